@@ -0,0 +1,169 @@
+use crate::{
+    commands::play::{normal_query_type_resolver, Mode, QueryType},
+    errors::{verify, ParrotError},
+    guild::{
+        cache::GuildCacheMap,
+        persisted_queue::PersistedTrack,
+        settings::{GuildSettings, GuildSettingsMap},
+    },
+    messaging::{
+        message::ParrotMessage,
+        messages::{FAIL_AUTOPLAY_NO_RELATED, FAIL_AUTOPLAY_NO_SEED},
+    },
+    sources::spotify::{Spotify, SPOTIFY},
+    utils::{create_response, get_youtube_video_id},
+};
+use serenity::{
+    all::CommandInteraction,
+    client::Context,
+    http::Http,
+    model::id::GuildId,
+    prelude::{Mutex, RwLock, TypeMap},
+};
+use songbird::{input::YoutubeDl, Call};
+use std::{collections::HashSet, sync::Arc};
+use tokio::process::Command;
+
+// how many tracks of the seed's mix are considered
+const MIX_SIZE: &str = "25";
+
+pub async fn autoplay(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.toggle_autoplay();
+    guild_settings.save()?;
+
+    if guild_settings.autoplay {
+        create_response(&ctx.http, interaction, ParrotMessage::AutoplayOn).await
+    } else {
+        create_response(&ctx.http, interaction, ParrotMessage::AutoplayOff).await
+    }
+}
+
+/// Queues a track related to the last played one, skipping anything that's still in the
+/// guild's history. Spotify's recommendations are asked first when Spotify is set up, and
+/// the YouTube mix of the track is used otherwise.
+pub async fn enqueue_related_track(
+    call: &Arc<Mutex<Call>>,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+) -> Result<(), ParrotError> {
+    let data_rlock = data.read().await;
+    let history: Vec<_> = data_rlock
+        .get::<GuildCacheMap>()
+        .and_then(|cache_map| cache_map.get(&guild_id))
+        .map(|cache| cache.history.iter().cloned().collect())
+        .unwrap_or_default();
+    drop(data_rlock);
+
+    let seed = verify(history.last(), ParrotError::Other(FAIL_AUTOPLAY_NO_SEED))?;
+
+    let played: HashSet<String> = history
+        .iter()
+        .map(|track| {
+            get_youtube_video_id(&track.source_url).unwrap_or_else(|| track.source_url.clone())
+        })
+        .collect();
+
+    let related_id = match find_spotify_related(seed.metadata.title.clone(), &played).await {
+        Some(id) => id,
+        None => find_mix_related(seed, &played).await?,
+    };
+
+    let query_type =
+        QueryType::VideoLink(format!("https://www.youtube.com/watch?v={}", related_id));
+    normal_query_type_resolver(
+        call,
+        http,
+        data,
+        guild_id,
+        &query_type,
+        Mode::End,
+        seed.requester,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Played tracks don't remember being found through a Spotify link once they've been searched
+/// on YouTube, so the seed is matched on Spotify by its title.
+async fn find_spotify_related(title: Option<String>, played: &HashSet<String>) -> Option<String> {
+    let title = title?;
+    let query_list = {
+        let spotify = SPOTIFY.lock().await;
+        let spotify = spotify.as_ref().ok()?;
+        Spotify::get_recommendations(spotify, &title).await.ok()?
+    };
+
+    for query in query_list {
+        match search_video_id(Some(query)).await {
+            Some(id) if !played.contains(&id) => return Some(id),
+            _ => continue,
+        }
+    }
+
+    None
+}
+
+async fn find_mix_related(
+    seed: &PersistedTrack,
+    played: &HashSet<String>,
+) -> Result<String, ParrotError> {
+    // mixes only exist for YouTube videos, so other sources are matched by title first
+    let seed_id = match get_youtube_video_id(&seed.source_url) {
+        Some(id) => Some(id),
+        None => search_video_id(seed.metadata.title.clone()).await,
+    };
+    let seed_id = verify(seed_id, ParrotError::Other(FAIL_AUTOPLAY_NO_RELATED))?;
+
+    let related_id = get_mix_video_ids(&seed_id)
+        .await?
+        .into_iter()
+        .find(|id| !played.contains(id));
+    verify(related_id, ParrotError::Other(FAIL_AUTOPLAY_NO_RELATED))
+}
+
+async fn search_video_id(title: Option<String>) -> Option<String> {
+    let results = YoutubeDl::new_search(reqwest::Client::new(), title?)
+        .search(Some(1))
+        .await
+        .ok()?;
+
+    let url = results.first()?.source_url.as_ref()?;
+    get_youtube_video_id(url)
+}
+
+async fn get_mix_video_ids(video_id: &str) -> Result<Vec<String>, ParrotError> {
+    let mix_url = format!("https://www.youtube.com/watch?v={0}&list=RD{0}", video_id);
+
+    let output = Command::new("yt-dlp")
+        .args([
+            "--flat-playlist",
+            "-j",
+            "--playlist-end",
+            MIX_SIZE,
+            "--ignore-config",
+            "--no-warnings",
+            &mix_url,
+        ])
+        .output()
+        .await?;
+
+    let ids = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|entry| entry["id"].as_str().map(String::from))
+        .collect();
+
+    Ok(ids)
+}
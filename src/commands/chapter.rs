@@ -0,0 +1,56 @@
+use crate::{
+    errors::{verify, ParrotError},
+    messaging::{
+        message::ParrotMessage,
+        messages::{FAIL_CHAPTER_NOT_FOUND, FAIL_NO_CHAPTERS},
+    },
+    sources::chapters::select_chapter,
+    utils::{create_response, get_playback_tempo, ChaptersTypeMapKey},
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn chapter(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let args = interaction.data.options.clone();
+    let target = args.first().unwrap().value.as_str().unwrap();
+
+    let handler = call.lock().await;
+    let track = handler
+        .queue()
+        .current()
+        .ok_or(ParrotError::NothingPlaying)?;
+    drop(handler);
+
+    let position = track.get_info().await.unwrap().position;
+
+    let typemap = track.typemap().read().await;
+    let chapters = typemap
+        .get::<ChaptersTypeMapKey>()
+        .filter(|chapters| !chapters.is_empty());
+    let chapters = verify(chapters, ParrotError::Other(FAIL_NO_CHAPTERS))?;
+
+    // chapters are timed against the source, not the sped up or slowed down playback
+    let tempo = get_playback_tempo(&typemap);
+
+    let index = select_chapter(chapters, position.mul_f64(tempo), target);
+    let index = verify(index, ParrotError::Other(FAIL_CHAPTER_NOT_FOUND))?;
+    let chapter = chapters[index].clone();
+    drop(typemap);
+
+    let _ = track.seek(chapter.start.div_f64(tempo));
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::Chapter {
+            title: chapter.title,
+        },
+    )
+    .await
+}
@@ -0,0 +1,48 @@
+use crate::{
+    errors::ParrotError,
+    guild::settings::{GuildSettings, GuildSettingsMap},
+    messaging::message::ParrotMessage,
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn crossfade(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let args = interaction.data.options.clone();
+    let seconds = args.first().map(|arg| arg.value.as_i64().unwrap() as u8);
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    let Some(seconds) = seconds else {
+        let seconds = guild_settings.crossfade;
+        drop(data);
+
+        return create_response(
+            &ctx.http,
+            interaction,
+            ParrotMessage::CrossfadeCurrent { seconds },
+        )
+        .await;
+    };
+
+    // queued tracks look the setting up as they near their end, so nothing needs reloading
+    guild_settings.set_crossfade(seconds);
+    guild_settings.save()?;
+    let seconds = guild_settings.crossfade;
+    drop(data);
+
+    if seconds == 0 {
+        create_response(&ctx.http, interaction, ParrotMessage::CrossfadeOff).await
+    } else {
+        create_response(&ctx.http, interaction, ParrotMessage::Crossfade { seconds }).await
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    commands::remove::{remove_stored_entries, remove_tracks},
+    errors::ParrotError,
+    guild::settings::is_same_track,
+    handlers::track_end::update_queue_messages,
+    messaging::message::ParrotMessage,
+    utils::{create_response, AuxMetadataTypeMapKey},
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn dedupe(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let handler = call.lock().await;
+    let queue = handler.queue().current_queue();
+
+    let mut seen_urls: Vec<String> = Vec::with_capacity(queue.len());
+    let mut duplicates = Vec::new();
+    let mut positions = Vec::new();
+
+    // the first occurrence of a track is kept, which is always the one being played
+    for (position, track) in queue.iter().enumerate() {
+        let typemap = track.typemap().read().await;
+        let Some(url) = typemap
+            .get::<AuxMetadataTypeMapKey>()
+            .and_then(|metadata| metadata.source_url.clone())
+        else {
+            continue;
+        };
+
+        if seen_urls.iter().any(|seen| is_same_track(seen, &url)) {
+            duplicates.push(track.clone());
+            positions.push(position);
+        } else {
+            seen_urls.push(url);
+        }
+    }
+
+    let queue = remove_tracks(&handler, &duplicates);
+    drop(handler);
+
+    remove_stored_entries(&ctx.data, guild_id, &positions).await;
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::RemoveDuplicates {
+            count: duplicates.len(),
+        },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::{
+    errors::ParrotError,
+    guild::settings::{DuplicatePolicy, GuildSettings, GuildSettingsMap},
+    messaging::message::ParrotMessage,
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+const DEFAULT_REJECT_WITHIN: usize = 10;
+
+pub async fn duplicates(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let args = interaction.data.options.clone();
+
+    let policy = args
+        .iter()
+        .find(|arg| arg.name == "policy")
+        .and_then(|arg| arg.value.as_str());
+
+    let tracks = args
+        .iter()
+        .find(|arg| arg.name == "tracks")
+        .and_then(|arg| arg.value.as_i64())
+        .map_or(DEFAULT_REJECT_WITHIN, |tracks| tracks as usize);
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    // without a policy, only the current one is shown
+    if let Some(policy) = policy {
+        guild_settings.duplicates = match policy {
+            "reject" => DuplicatePolicy::Reject,
+            "recent" => DuplicatePolicy::RejectWithin(tracks),
+            _ => DuplicatePolicy::Allow,
+        };
+        guild_settings.save()?;
+    }
+
+    let policy = guild_settings.duplicates;
+    drop(data);
+
+    create_response(&ctx.http, interaction, ParrotMessage::Duplicates { policy }).await
+}
@@ -0,0 +1,28 @@
+use crate::{
+    errors::ParrotError,
+    guild::settings::{GuildSettings, GuildSettingsMap},
+    messaging::message::ParrotMessage,
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn fair_queue(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.toggle_fair_queue();
+    guild_settings.save()?;
+
+    if guild_settings.fair_queue {
+        create_response(&ctx.http, interaction, ParrotMessage::FairQueueOn).await
+    } else {
+        create_response(&ctx.http, interaction, ParrotMessage::FairQueueOff).await
+    }
+}
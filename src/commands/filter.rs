@@ -0,0 +1,63 @@
+use crate::{
+    commands::play::reload_queue,
+    errors::ParrotError,
+    filters::{format_filters, AudioFilter},
+    guild::settings::{GuildSettings, GuildSettingsMap},
+    handlers::track_end::update_queue_messages,
+    messaging::{message::ParrotMessage, messages::FAIL_INVALID_FILTER},
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context};
+use std::str::FromStr;
+
+pub const FILTER_OFF: &str = "off";
+
+pub async fn filter(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let args = interaction.data.options.clone();
+    let preset = args.first().unwrap().value.as_str().unwrap();
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    if preset == FILTER_OFF {
+        guild_settings.clear_filters();
+    } else {
+        let filter =
+            AudioFilter::from_str(preset).map_err(|_| ParrotError::Other(FAIL_INVALID_FILTER))?;
+        guild_settings.toggle_filter(filter);
+    }
+
+    guild_settings.save()?;
+    let filters = guild_settings.filters.clone();
+    drop(data);
+
+    // restart the current track (and whatever is queued) with the new filter graph
+    let queue = reload_queue(&call, &ctx.http, &ctx.data, guild_id).await?;
+
+    if filters.is_empty() {
+        create_response(&ctx.http, interaction, ParrotMessage::FiltersOff).await?;
+    } else {
+        create_response(
+            &ctx.http,
+            interaction,
+            ParrotMessage::Filters {
+                filters: format_filters(&filters),
+            },
+        )
+        .await?;
+    }
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
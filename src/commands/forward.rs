@@ -0,0 +1,25 @@
+use crate::{
+    commands::seek::seek_current_track,
+    errors::{verify, ParrotError},
+    messaging::messages::FAIL_INVALID_TIMESTAMP,
+    utils::{parse_timestamp, SeekTarget},
+};
+use serenity::{all::CommandInteraction, client::Context};
+use std::time::Duration;
+
+const DEFAULT_SEEK_OFFSET: Duration = Duration::from_secs(10);
+
+pub async fn forward(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let args = interaction.data.options.clone();
+
+    let offset = match args.first() {
+        Some(arg) => parse_timestamp(arg.value.as_str().unwrap()),
+        None => Some(DEFAULT_SEEK_OFFSET),
+    };
+    let offset = verify(offset, ParrotError::Other(FAIL_INVALID_TIMESTAMP))?;
+
+    seek_current_track(ctx, interaction, SeekTarget::Forward(offset)).await
+}
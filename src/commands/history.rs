@@ -0,0 +1,131 @@
+use crate::{
+    commands::queue::{build_nav_btns, count_pages, turn_page, EMBED_PAGE_SIZE, EMBED_TIMEOUT},
+    errors::ParrotError,
+    guild::{cache::GuildCacheMap, persisted_queue::PersistedTrack},
+    messaging::messages::{HISTORY_EMPTY, HISTORY_TITLE, QUEUE_EXPIRED, QUEUE_PAGE, QUEUE_PAGE_OF},
+    utils::get_human_readable_timestamp,
+};
+use serenity::{
+    all::{
+        CommandInteraction, CreateEmbedFooter, CreateInteractionResponse,
+        CreateInteractionResponseMessage, EditMessage,
+    },
+    builder::CreateEmbed,
+    client::Context,
+    futures::StreamExt,
+    model::{id::GuildId, mention::Mentionable},
+    prelude::{RwLock, TypeMap},
+};
+use std::{fmt::Write, sync::Arc, time::Duration};
+
+pub async fn history(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let tracks = get_history(&ctx.data, guild_id).await;
+    let num_pages = count_pages(tracks.len());
+    let message = build_nav_btns(
+        CreateInteractionResponseMessage::new().add_embed(create_history_embed(&tracks, 0)),
+        0,
+        num_pages,
+    );
+
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+        .await?;
+
+    let mut message = interaction.get_response(&ctx.http).await?;
+    let mut page: usize = 0;
+
+    let mut cib = message
+        .await_component_interactions(ctx)
+        .timeout(Duration::from_secs(EMBED_TIMEOUT))
+        .stream();
+
+    while let Some(mci) = cib.next().await {
+        let btn_id = &mci.data.custom_id;
+
+        // refetch the history in case more tracks were played
+        let tracks = get_history(&ctx.data, guild_id).await;
+        let num_pages = count_pages(tracks.len());
+
+        page = match turn_page(btn_id, page, num_pages) {
+            Some(page) => page,
+            None => continue,
+        };
+
+        let message = build_nav_btns(
+            CreateInteractionResponseMessage::new().add_embed(create_history_embed(&tracks, page)),
+            page,
+            num_pages,
+        );
+
+        mci.create_response(&ctx, CreateInteractionResponse::UpdateMessage(message))
+            .await?;
+    }
+
+    message
+        .edit(
+            &ctx.http,
+            EditMessage::new().add_embed(CreateEmbed::new().description(QUEUE_EXPIRED)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The guild's played tracks, the most recent one first.
+async fn get_history(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) -> Vec<PersistedTrack> {
+    let data = data.read().await;
+
+    data.get::<GuildCacheMap>()
+        .and_then(|cache_map| cache_map.get(&guild_id))
+        .map(|cache| cache.history.iter().rev().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn create_history_embed(tracks: &[PersistedTrack], page: usize) -> CreateEmbed {
+    CreateEmbed::new()
+        .field(HISTORY_TITLE, build_history_page(tracks, page), false)
+        .footer(CreateEmbedFooter::new(format!(
+            "{} {} {} {}",
+            QUEUE_PAGE,
+            page + 1,
+            QUEUE_PAGE_OF,
+            count_pages(tracks.len()),
+        )))
+}
+
+fn build_history_page(tracks: &[PersistedTrack], page: usize) -> String {
+    let start_idx = EMBED_PAGE_SIZE * page;
+    let history: Vec<&PersistedTrack> = tracks
+        .iter()
+        .skip(start_idx)
+        .take(EMBED_PAGE_SIZE)
+        .collect();
+
+    if history.is_empty() {
+        return String::from(HISTORY_EMPTY);
+    }
+
+    let mut description = String::new();
+
+    for (i, track) in history.iter().enumerate() {
+        let title = track.metadata.title.as_deref().unwrap_or_default();
+        let duration = get_human_readable_timestamp(track.metadata.duration);
+
+        let _ = writeln!(
+            description,
+            "`{}.` [{}]({}) • `{}` • {}",
+            i + start_idx + 1,
+            title,
+            track.source_url,
+            duration,
+            track.requester.mention()
+        );
+    }
+
+    description
+}
@@ -0,0 +1,36 @@
+use crate::{
+    commands::{
+        play::rotate_upcoming,
+        skip::{create_skip_response, force_skip_top_track},
+    },
+    errors::{verify, ParrotError},
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn jump(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let args = interaction.data.options.clone();
+    let index = args.first().unwrap().value.as_i64().unwrap() as isize;
+
+    let handler = call.lock().await;
+    let queue_len = handler.queue().len() as isize;
+
+    verify(queue_len > 1, ParrotError::QueueEmpty)?;
+    verify(
+        index < queue_len,
+        ParrotError::NotInRange("index", index, 1, queue_len - 1),
+    )?;
+
+    // the tracks jumped over wrap around to the back of the queue instead of being dropped
+    let skipped = index as usize - 1;
+    let upcoming = queue_len as usize - 1;
+    handler
+        .queue()
+        .modify_queue(|queue| rotate_upcoming(queue.make_contiguous(), upcoming - skipped));
+
+    force_skip_top_track(&handler).await?;
+    create_skip_response(ctx, interaction, &handler, 1).await
+}
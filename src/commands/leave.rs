@@ -1,6 +1,8 @@
 use crate::{
-    errors::ParrotError, guild::stored_queue::GuildStoredQueueMap,
-    messaging::message::ParrotMessage, utils::create_response,
+    errors::ParrotError,
+    guild::{persisted_queue::PersistedQueue, stored_queue::GuildStoredQueueMap},
+    messaging::message::ParrotMessage,
+    utils::create_response,
 };
 use serenity::{all::CommandInteraction, client::Context};
 
@@ -23,6 +25,7 @@ pub async fn leave(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
     drop(handler);
 
     manager.remove(guild_id).await.unwrap();
+    PersistedQueue::delete(guild_id)?;
 
     create_response(&ctx.http, interaction, ParrotMessage::Leaving).await
 }
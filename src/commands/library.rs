@@ -0,0 +1,52 @@
+use crate::{
+    errors::{verify, ParrotError},
+    messaging::{message::ParrotMessage, messages::FAIL_LIBRARY_DISABLED},
+    sources::library::{Library, LIBRARY, LIBRARY_PATH},
+    utils::{create_response, edit_response},
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn library(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    verify(
+        LIBRARY_PATH.is_some(),
+        ParrotError::Other(FAIL_LIBRARY_DISABLED),
+    )?;
+
+    let args = interaction.data.options.clone();
+    let subcommand = args.first().unwrap();
+
+    match subcommand.name.as_str() {
+        "rescan" => {
+            // reading the tags of a large library takes a while
+            create_response(&ctx.http, interaction, ParrotMessage::LibraryScanning).await?;
+
+            let count = Library::rescan().await?;
+            edit_response(
+                &ctx.http,
+                interaction,
+                ParrotMessage::LibraryScanned { count },
+            )
+            .await?;
+
+            Ok(())
+        }
+        _ => {
+            let stats = LIBRARY.read().await.stats();
+
+            create_response(
+                &ctx.http,
+                interaction,
+                ParrotMessage::LibraryStats {
+                    tracks: stats.tracks,
+                    artists: stats.artists,
+                    albums: stats.albums,
+                    duration: stats.duration,
+                },
+            )
+            .await
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    errors::ParrotError,
+    guild::settings::{GuildSettings, GuildSettingsMap},
+    messaging::message::ParrotMessage,
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context};
+use std::time::Duration;
+
+pub async fn limits(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let args = interaction.data.options.clone();
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    // zero lifts a limit
+    for arg in args.iter() {
+        let value = arg.value.as_i64().unwrap() as u64;
+        let limit = (value > 0).then_some(value);
+
+        match arg.name.as_str() {
+            "queue" => guild_settings.max_queue_length = limit.map(|max| max as usize),
+            "per_user" => guild_settings.max_tracks_per_user = limit.map(|max| max as usize),
+            "playlist" => guild_settings.max_playlist_size = limit.map(|max| max as usize),
+            "duration" => {
+                guild_settings.max_track_duration = limit.map(|max| max.saturating_mul(60))
+            }
+            _ => {}
+        }
+    }
+
+    if !args.is_empty() {
+        guild_settings.save()?;
+    }
+
+    let message = ParrotMessage::Limits {
+        queue_length: guild_settings.max_queue_length,
+        tracks_per_user: guild_settings.max_tracks_per_user,
+        playlist_size: guild_settings.max_playlist_size,
+        track_duration: guild_settings.max_track_duration.map(Duration::from_secs),
+    };
+    drop(data);
+
+    create_response(&ctx.http, interaction, message).await
+}
@@ -0,0 +1,114 @@
+use crate::{
+    errors::{verify, ParrotError},
+    handlers::LoopSectionHandler,
+    messaging::{
+        message::ParrotMessage,
+        messages::{FAIL_INVALID_LOOP_SECTION, FAIL_INVALID_TIMESTAMP, FAIL_NO_LOOP_SECTION},
+    },
+    utils::{
+        create_response, get_playback_tempo, parse_timestamp, AuxMetadataTypeMapKey, LoopSection,
+        LoopSectionHandlerTypeMapKey, LoopSectionTypeMapKey,
+    },
+};
+use serenity::{
+    all::{CommandDataOptionValue, CommandInteraction},
+    client::Context,
+};
+use songbird::{tracks::TrackHandle, Event};
+use std::time::Duration;
+
+const LOOP_SECTION_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+pub async fn loop_section(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let handler = call.lock().await;
+    let track = handler
+        .queue()
+        .current()
+        .ok_or(ParrotError::NothingPlaying)?;
+    drop(handler);
+
+    let args = interaction.data.options.clone();
+    let subcommand = args.first().unwrap();
+
+    let CommandDataOptionValue::SubCommand(options) = &subcommand.value else {
+        unreachable!()
+    };
+
+    if subcommand.name == "off" {
+        let removed = track
+            .typemap()
+            .write()
+            .await
+            .remove::<LoopSectionTypeMapKey>();
+        verify(removed, ParrotError::Other(FAIL_NO_LOOP_SECTION))?;
+
+        return create_response(&ctx.http, interaction, ParrotMessage::LoopSectionOff).await;
+    }
+
+    let get_timestamp = |name: &str| {
+        let value = options
+            .iter()
+            .find(|option| option.name == name)
+            .and_then(|option| option.value.as_str())
+            .unwrap();
+        let timestamp = parse_timestamp(value);
+        verify(timestamp, ParrotError::Other(FAIL_INVALID_TIMESTAMP))
+            .map(|timestamp| (value.to_owned(), timestamp))
+    };
+
+    let (start_str, start) = get_timestamp("start")?;
+    let (end_str, end) = get_timestamp("end")?;
+
+    let duration = track
+        .typemap()
+        .read()
+        .await
+        .get::<AuxMetadataTypeMapKey>()
+        .and_then(|metadata| metadata.duration);
+
+    let is_valid = end > start && duration.map_or(true, |duration| end <= duration);
+    verify(is_valid, ParrotError::Other(FAIL_INVALID_LOOP_SECTION))?;
+
+    start_loop_section(&track, LoopSection { start, end }).await;
+
+    let tempo = get_playback_tempo(&*track.typemap().read().await);
+    let _ = track.seek(start.div_f64(tempo));
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::LoopSection {
+            start: start_str,
+            end: end_str,
+        },
+    )
+    .await
+}
+
+/// Starts looping the given section of a track, replacing any section that
+/// was being looped before.
+pub async fn start_loop_section(track: &TrackHandle, section: LoopSection) {
+    let mut typemap = track.typemap().write().await;
+    typemap.insert::<LoopSectionTypeMapKey>(section);
+
+    // a handler that's still running picks up the new section by itself
+    if typemap.contains_key::<LoopSectionHandlerTypeMapKey>() {
+        return;
+    }
+
+    let added = track.add_event(
+        Event::Periodic(LOOP_SECTION_CHECK_INTERVAL, None),
+        LoopSectionHandler,
+    );
+
+    if added.is_ok() {
+        typemap.insert::<LoopSectionHandlerTypeMapKey>(());
+    }
+}
@@ -1,19 +1,45 @@
 pub mod autopause;
+pub mod autoplay;
+pub mod chapter;
 pub mod clear;
+pub mod crossfade;
+pub mod dedupe;
+pub mod duplicates;
+pub mod fair_queue;
+pub mod filter;
+pub mod forward;
+pub mod history;
+pub mod jump;
 pub mod leave;
+pub mod library;
+pub mod limits;
+pub mod loop_section;
 pub mod manage_sources;
+pub mod move_track;
+pub mod normalize;
 pub mod now_playing;
 pub mod pause;
+pub mod pitch;
 pub mod play;
+pub mod playlist;
+pub mod previous;
 pub mod queue;
+pub mod queue_file;
 pub mod remove;
+pub mod remove_gone;
+pub mod remove_user;
 pub mod repeat;
 pub mod repeat_queue;
 pub mod resume;
+pub mod rewind;
 pub mod seek;
 pub mod shuffle;
 pub mod skip;
+pub mod speed;
+pub mod sponsorblock;
 pub mod stop;
 pub mod summon;
+pub mod swap;
 pub mod version;
+pub mod volume;
 pub mod voteskip;
@@ -0,0 +1,82 @@
+use crate::{
+    errors::{verify, ParrotError},
+    guild::stored_queue::GuildStoredQueueMap,
+    handlers::track_end::update_queue_messages,
+    messaging::message::ParrotMessage,
+    utils::{create_response, AuxMetadataTypeMapKey},
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn move_track(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let args = interaction.data.options.clone();
+    let from = args.first().unwrap().value.as_i64().unwrap() as isize;
+    let to = args.get(1).unwrap().value.as_i64().unwrap() as isize;
+
+    let handler = call.lock().await;
+    let queue = handler.queue().current_queue();
+    let queue_len = queue.len() as isize;
+
+    verify(queue_len > 1, ParrotError::QueueEmpty)?;
+    verify(
+        from < queue_len,
+        ParrotError::NotInRange("from", from, 1, queue_len - 1),
+    )?;
+    verify(
+        to < queue_len,
+        ParrotError::NotInRange("to", to, 1, queue_len - 1),
+    )?;
+
+    let (from, to) = (from as usize, to as usize);
+    let metadata = queue[from]
+        .typemap()
+        .read()
+        .await
+        .get::<AuxMetadataTypeMapKey>()
+        .unwrap()
+        .clone();
+
+    let mut data = ctx.data.write().await;
+    let guild_stored_queue = data
+        .get_mut::<GuildStoredQueueMap>()
+        .unwrap()
+        .get_mut(&guild_id)
+        .unwrap();
+
+    handler.queue().modify_queue(|v| {
+        let track = v.remove(from).unwrap();
+        v.insert(to, track);
+
+        // tracks moved past the stored ones still come back last when the queue loops
+        if from < guild_stored_queue.queue.len() {
+            let stored = guild_stored_queue.queue.remove(from);
+            let to = to.min(guild_stored_queue.queue.len());
+            guild_stored_queue.queue.insert(to, stored);
+        }
+    });
+
+    // refetch the queue after modification
+    let queue = handler.queue().current_queue();
+    drop(handler);
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::Move {
+            title: metadata.title.unwrap(),
+            url: metadata.source_url.unwrap(),
+            position: to,
+        },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
@@ -1,35 +1,55 @@
 use crate::{
-    commands::{skip::force_skip_top_track, summon::summon},
+    commands::{loop_section::start_loop_section, skip::force_skip_top_track, summon::summon},
     errors::{verify, ParrotError},
+    filters::{build_filter_graph, calculate_tempo, AudioFilters, PlaybackRate},
     guild::{
-        settings::{GuildSettings, GuildSettingsMap},
+        cache::GuildCacheMap,
+        persisted_queue::PersistedTrack,
+        settings::{DuplicatePolicy, GuildSettings, GuildSettingsMap, QueueLimit, DEFAULT_VOLUME},
         stored_queue::{GuildStoredQueue, GuildStoredQueueMap},
     },
-    handlers::track_end::update_queue_messages,
+    handlers::{
+        track_end::update_queue_messages, ChaptersHandler, CrossfadeHandler, EndOffsetHandler,
+        SkipSegmentsHandler, StartOffsetHandler, StreamTitleHandler,
+    },
     messaging::{
         message::ParrotMessage,
-        messages::{PLAY_QUEUE, PLAY_TOP, SPOTIFY_AUTH_FAILED, TRACK_DURATION, TRACK_TIME_TO_PLAY},
+        messages::{
+            FAIL_ATTACHMENT_NOT_MEDIA, FAIL_ATTACHMENT_TOO_LARGE, FAIL_LIBRARY_DISABLED,
+            FAIL_PLAY_NO_QUERY, PLAY_QUEUE, PLAY_TOP, SPOTIFY_AUTH_FAILED, TRACK_DURATION,
+            TRACK_REQUESTED_BY, TRACK_START_OFFSET, TRACK_TIME_TO_PLAY,
+        },
+    },
+    sources::{
+        attachment::{AttachmentSource, MAX_ATTACHMENT_SIZE},
+        ffmpeg::FfmpegSource,
+        library::Library,
+        radio::{RadioSource, RadioStream},
+        sponsorblock::{SponsorBlock, SPONSORBLOCK_API_URL},
+        spotify::{Spotify, SPOTIFY},
     },
-    sources::spotify::{Spotify, SPOTIFY},
     utils::{
-        compare_domains, create_now_playing_embed, create_response, edit_embed_response,
-        edit_response, get_human_readable_timestamp, AuxMetadataTypeMapKey,
+        create_now_playing_embed, create_response, edit_embed_response, edit_response,
+        get_human_readable_timestamp, get_playback_range, get_playback_tempo, get_youtube_video_id,
+        volume_to_gain, AudioFiltersTypeMapKey, AuxMetadataTypeMapKey, LoopSectionTypeMapKey,
+        PlaybackRange, PlaybackRangeTypeMapKey, PlaybackRateTypeMapKey, ReplacedTypeMapKey,
+        RequesterTypeMapKey,
     },
 };
 use serenity::{
-    all::{CommandInteraction, CreateEmbedFooter},
+    all::{CommandDataOptionValue, CommandInteraction, CreateEmbedFooter},
     builder::CreateEmbed,
     client::Context,
-    futures::executor::block_on,
     http::Http,
-    model::id::GuildId,
-    prelude::Mutex,
+    model::id::{GuildId, UserId},
+    prelude::{Mentionable, Mutex},
 };
 use songbird::{
-    input::{Compose, YoutubeDl},
-    tracks::TrackHandle,
+    events::EventData,
+    input::{AuxMetadata, Compose, File, HttpRequest, Input, YoutubeDl},
+    tracks::{LoopState, PlayMode, Track, TrackHandle},
     typemap::TypeMap,
-    Call,
+    Call, Event, TrackEvent,
 };
 use std::{cmp::Ordering, error::Error as StdError, sync::Arc, time::Duration};
 use tokio::sync::RwLock;
@@ -37,6 +57,12 @@ use url::Url;
 
 use reqwest;
 
+const PRELOAD_TIME: Duration = Duration::from_secs(5);
+const CROSSFADE_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+const SKIP_SEGMENTS_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+const END_OFFSET_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+const STREAM_TITLE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Copy, Debug)]
 pub enum Mode {
     End,
@@ -47,12 +73,93 @@ pub enum Mode {
     Jump,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum QueryType {
     Keywords(String),
     KeywordList(Vec<String>),
     VideoLink(String),
     PlaylistLink(String),
+    File(String),
+    Library(String),
+    Radio(String),
+}
+
+impl QueryType {
+    /// The query that queues a track again from the link it was played from.
+    pub fn from_source_url(url: String) -> QueryType {
+        if Library::is_library_url(&url) {
+            QueryType::Library(url)
+        } else if AttachmentSource::is_attachment_url(&url) {
+            QueryType::File(url)
+        } else if RadioSource::is_playlist_url(&url) {
+            QueryType::Radio(url)
+        } else {
+            QueryType::VideoLink(url)
+        }
+    }
+}
+
+/// A link or search terms given to /play, unless the guild blocks where they'd come from.
+pub enum ParsedQuery {
+    Allowed(QueryType),
+    BlockedDomain(String),
+}
+
+/// Works out how a link or search terms are queued. Spotify links are resolved into
+/// searches right away, and search terms count as coming from YouTube.
+pub async fn parse_query(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    query: &str,
+) -> Result<ParsedQuery, ParrotError> {
+    let (domain, query_type) = match Url::parse(query) {
+        Ok(url_data) => match url_data.host_str() {
+            Some("open.spotify.com") => {
+                let spotify = SPOTIFY.lock().await;
+                let spotify = verify(spotify.as_ref(), ParrotError::Other(SPOTIFY_AUTH_FAILED))?;
+                let query_type = Spotify::extract(spotify, query).await?;
+                return Ok(ParsedQuery::Allowed(query_type));
+            }
+            Some(domain) if RadioSource::is_playlist_url(query) => {
+                (domain.to_string(), QueryType::Radio(query.to_string()))
+            }
+            Some(domain) if query.contains("list=") => (
+                domain.to_string(),
+                QueryType::PlaylistLink(query.to_string()),
+            ),
+            Some(domain) => (domain.to_string(), QueryType::VideoLink(query.to_string())),
+            None => {
+                return Err(ParrotError::Other(
+                    "Something went wrong while parsing your query!",
+                ))
+            }
+        },
+        Err(_) => (
+            String::from("youtube.com"),
+            QueryType::Keywords(query.to_string()),
+        ),
+    };
+
+    if is_domain_allowed(data, guild_id, &domain).await {
+        Ok(ParsedQuery::Allowed(query_type))
+    } else {
+        Ok(ParsedQuery::BlockedDomain(domain))
+    }
+}
+
+/// Whether the guild's allowed and banned domains let tracks be played from `domain`.
+pub async fn is_domain_allowed(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    domain: &str,
+) -> bool {
+    let mut data = data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    guild_settings.is_domain_allowed(domain)
 }
 
 pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
@@ -68,99 +175,81 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
         _ => Mode::End,
     };
 
-    let url = match mode {
-        Mode::End => first_arg.value.as_str().unwrap(),
-        _ => first_arg.value.as_str().unwrap(),
+    // /superplay's options are nested under the chosen subcommand
+    let options = match &first_arg.value {
+        CommandDataOptionValue::SubCommand(options) => options.clone(),
+        _ => args.clone(),
     };
 
+    let url = options
+        .iter()
+        .find(|option| option.name == "query")
+        .and_then(|option| option.value.as_str());
+
+    let library = options
+        .iter()
+        .find(|option| option.name == "library")
+        .and_then(|option| option.value.as_str());
+
+    let attachment = options
+        .iter()
+        .find(|option| option.name == "file")
+        .and_then(|option| option.value.as_attachment_id())
+        .and_then(|attachment_id| interaction.data.resolved.attachments.get(&attachment_id))
+        .cloned();
+
+    // nothing to play shouldn't pull the bot into a voice channel
+    verify(
+        attachment.is_some() || library.is_some() || url.is_some(),
+        ParrotError::Other(FAIL_PLAY_NO_QUERY),
+    )?;
+
+    if let Some(attachment) = &attachment {
+        let is_media = attachment.content_type.as_deref().map_or(false, |mime| {
+            mime.starts_with("audio/") || mime.starts_with("video/")
+        });
+        verify(is_media, ParrotError::Other(FAIL_ATTACHMENT_NOT_MEDIA))?;
+
+        let is_too_large = u64::from(attachment.size) > *MAX_ATTACHMENT_SIZE;
+        verify(!is_too_large, ParrotError::Other(FAIL_ATTACHMENT_TOO_LARGE))?;
+    }
+
     let guild_id = interaction.guild_id.unwrap();
+    let requester = interaction.user.id;
     let manager = songbird::get(ctx).await.unwrap();
 
     // try to join a voice channel if not in one just yet
     summon(ctx, interaction, false).await?;
     let call = manager.get(guild_id).unwrap();
 
-    // determine whether this is a link or a query string
-    let query_type = match Url::parse(url) {
-        Ok(url_data) => match url_data.host_str() {
-            Some("open.spotify.com") => {
-                let spotify = SPOTIFY.lock().await;
-                let spotify = verify(spotify.as_ref(), ParrotError::Other(SPOTIFY_AUTH_FAILED))?;
-                Some(Spotify::extract(spotify, url).await?)
-            }
-            Some(other) => {
-                let mut data = ctx.data.write().await;
-                let settings = data.get_mut::<GuildSettingsMap>().unwrap();
-                let guild_settings = settings
-                    .entry(guild_id)
-                    .or_insert_with(|| GuildSettings::new(guild_id));
-
-                let is_allowed = guild_settings
-                    .allowed_domains
-                    .iter()
-                    .any(|d| compare_domains(d, other));
-
-                let is_banned = guild_settings
-                    .banned_domains
-                    .iter()
-                    .any(|d| compare_domains(d, other));
-
-                if is_banned || (guild_settings.banned_domains.is_empty() && !is_allowed) {
-                    return create_response(
-                        &ctx.http,
-                        interaction,
-                        ParrotMessage::PlayDomainBanned {
-                            domain: other.to_string(),
-                        },
-                    )
-                    .await;
-                }
-
-                if url.contains("list=") {
-                    Some(QueryType::PlaylistLink(url.to_string()))
-                } else {
-                    Some(QueryType::VideoLink(url.to_string()))
-                }
-            }
-            None => None,
-        },
-        Err(_) => {
-            let mut data = ctx.data.write().await;
-            let settings = data.get_mut::<GuildSettingsMap>().unwrap();
-            let guild_settings = settings
-                .entry(guild_id)
-                .or_insert_with(|| GuildSettings::new(guild_id));
-
-            if guild_settings.banned_domains.contains("youtube.com")
-                || (guild_settings.banned_domains.is_empty()
-                    && !guild_settings.allowed_domains.contains("youtube.com"))
-            {
+    // determine whether this is an upload, a link or a query string
+    let query_type = match (attachment, library, url) {
+        // uploads and the library don't come from the internet, so the domain rules don't apply
+        (Some(attachment), _, _) => QueryType::File(attachment.url),
+        (None, Some(query), _) => QueryType::Library(query.to_string()),
+        (None, None, Some(url)) => match parse_query(&ctx.data, guild_id, url).await? {
+            ParsedQuery::Allowed(query_type) => query_type,
+            ParsedQuery::BlockedDomain(domain) => {
                 return create_response(
                     &ctx.http,
                     interaction,
-                    ParrotMessage::PlayDomainBanned {
-                        domain: "youtube.com".to_string(),
-                    },
+                    ParrotMessage::PlayDomainBanned { domain },
                 )
                 .await;
             }
-
-            Some(QueryType::Keywords(url.to_string()))
-        }
+        },
+        (None, None, None) => unreachable!(),
     };
 
-    let query_type = verify(
-        query_type,
-        ParrotError::Other("Something went wrong while parsing your query!"),
-    )?;
-
     let mut data = ctx.data.write().await;
     let stored_queue_map = data.get_mut::<GuildStoredQueueMap>().unwrap();
     let guild_stored_queue = stored_queue_map
         .entry(guild_id)
         .or_insert_with(GuildStoredQueue::new);
 
-    guild_stored_queue.queue.push(query_type.clone());
+    guild_stored_queue
+        .queue
+        .push((query_type.clone(), requester));
     guild_stored_queue.continue_play = true;
     drop(data);
 
@@ -172,38 +261,105 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
     let queue_was_empty = handler.queue().is_empty();
     drop(handler);
 
+    let mut summary = PlaylistSummary::default();
+
     match mode {
         Mode::End => {
-            normal_query_type_resolver(&call, &ctx.http, &ctx.data, guild_id, &query_type, mode)
-                .await?
+            summary = normal_query_type_resolver(
+                &call,
+                &ctx.http,
+                &ctx.data,
+                guild_id,
+                &query_type,
+                mode,
+                requester,
+            )
+            .await?
         }
         Mode::Next => match query_type.clone() {
-            QueryType::Keywords(_) | QueryType::VideoLink(_) => {
-                let queue = insert_track(&call, &query_type, 1).await?;
+            QueryType::Keywords(_)
+            | QueryType::VideoLink(_)
+            | QueryType::File(_)
+            | QueryType::Library(_)
+            | QueryType::Radio(_) => {
+                let queue = insert_track(
+                    &call,
+                    &ctx.http,
+                    &ctx.data,
+                    guild_id,
+                    &query_type,
+                    1,
+                    requester,
+                )
+                .await?;
                 update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
             }
             QueryType::PlaylistLink(url) => {
-                let urls = get_urls_from_playlist(url, None).await?;
+                let mut urls = get_urls_from_playlist(url, None).await?;
+                summary.limit_hit = limit_playlist(&mut urls, &ctx.data, guild_id).await;
 
                 for (idx, url) in urls.into_iter().flatten().enumerate() {
-                    let Ok(queue) = insert_track(&call, &QueryType::VideoLink(url), idx + 1).await
-                    else {
-                        continue;
+                    let queue = match insert_track(
+                        &call,
+                        &ctx.http,
+                        &ctx.data,
+                        guild_id,
+                        &QueryType::VideoLink(url),
+                        idx + 1,
+                        requester,
+                    )
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => match summary.record_failure(err) {
+                            Ok(false) => break,
+                            _ => continue,
+                        },
                     };
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
-            QueryType::KeywordList(keywords_list) => {
+            QueryType::KeywordList(mut keywords_list) => {
+                summary.limit_hit = limit_playlist(&mut keywords_list, &ctx.data, guild_id).await;
+
                 for (idx, keywords) in keywords_list.into_iter().enumerate() {
-                    let queue =
-                        insert_track(&call, &QueryType::Keywords(keywords), idx + 1).await?;
+                    let queue = match insert_track(
+                        &call,
+                        &ctx.http,
+                        &ctx.data,
+                        guild_id,
+                        &QueryType::Keywords(keywords),
+                        idx + 1,
+                        requester,
+                    )
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => match summary.record_failure(err) {
+                            Ok(false) => break,
+                            Ok(true) => continue,
+                            Err(err) => return Err(err),
+                        },
+                    };
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
         },
         Mode::Jump => match query_type.clone() {
-            QueryType::Keywords(_) | QueryType::VideoLink(_) => {
-                let mut queue = enqueue_track(&call, &query_type).await?;
+            QueryType::Keywords(_)
+            | QueryType::VideoLink(_)
+            | QueryType::File(_)
+            | QueryType::Library(_)
+            | QueryType::Radio(_) => {
+                let mut queue = append_track(
+                    &call,
+                    &ctx.http,
+                    &ctx.data,
+                    guild_id,
+                    &query_type,
+                    requester,
+                )
+                .await?;
 
                 if !queue_was_empty {
                     rotate_tracks(&call, 1).await.ok();
@@ -213,15 +369,28 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
                 update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
             }
             QueryType::PlaylistLink(url) => {
-                let urls = get_urls_from_playlist(url, None).await?;
+                let mut urls = get_urls_from_playlist(url, None).await?;
+                summary.limit_hit = limit_playlist(&mut urls, &ctx.data, guild_id).await;
 
                 let mut insert_idx = 1;
 
                 for (i, url) in urls.into_iter().flatten().enumerate() {
-                    let Ok(mut queue) =
-                        insert_track(&call, &QueryType::VideoLink(url), insert_idx).await
-                    else {
-                        continue;
+                    let mut queue = match insert_track(
+                        &call,
+                        &ctx.http,
+                        &ctx.data,
+                        guild_id,
+                        &QueryType::VideoLink(url),
+                        insert_idx,
+                        requester,
+                    )
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => match summary.record_failure(err) {
+                            Ok(false) => break,
+                            _ => continue,
+                        },
                     };
 
                     if i == 0 && !queue_was_empty {
@@ -233,12 +402,29 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
-            QueryType::KeywordList(keywords_list) => {
+            QueryType::KeywordList(mut keywords_list) => {
+                summary.limit_hit = limit_playlist(&mut keywords_list, &ctx.data, guild_id).await;
                 let mut insert_idx = 1;
 
                 for (i, keywords) in keywords_list.into_iter().enumerate() {
-                    let mut queue =
-                        insert_track(&call, &QueryType::Keywords(keywords), insert_idx).await?;
+                    let mut queue = match insert_track(
+                        &call,
+                        &ctx.http,
+                        &ctx.data,
+                        guild_id,
+                        &QueryType::Keywords(keywords),
+                        insert_idx,
+                        requester,
+                    )
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => match summary.record_failure(err) {
+                            Ok(false) => break,
+                            Ok(true) => continue,
+                            Err(err) => return Err(err),
+                        },
+                    };
 
                     if i == 0 && !queue_was_empty {
                         queue = force_skip_top_track(&call.lock().await).await?;
@@ -252,18 +438,50 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
         },
         Mode::All | Mode::Reverse | Mode::Shuffle => match query_type.clone() {
             QueryType::VideoLink(url) | QueryType::PlaylistLink(url) => {
-                let urls = get_urls_from_playlist(url, None).await?;
+                let mut urls = get_urls_from_playlist(url, None).await?;
+                summary.limit_hit = limit_playlist(&mut urls, &ctx.data, guild_id).await;
 
                 for url in urls.into_iter().flatten() {
-                    let Ok(queue) = enqueue_track(&call, &QueryType::VideoLink(url)).await else {
-                        continue;
+                    let queue = match enqueue_track(
+                        &call,
+                        &ctx.http,
+                        &ctx.data,
+                        guild_id,
+                        &QueryType::VideoLink(url),
+                        requester,
+                    )
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => match summary.record_failure(err) {
+                            Ok(false) => break,
+                            _ => continue,
+                        },
                     };
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
-            QueryType::KeywordList(keywords_list) => {
+            QueryType::KeywordList(mut keywords_list) => {
+                summary.limit_hit = limit_playlist(&mut keywords_list, &ctx.data, guild_id).await;
+
                 for keywords in keywords_list.into_iter() {
-                    let queue = enqueue_track(&call, &QueryType::Keywords(keywords)).await?;
+                    let queue = match enqueue_track(
+                        &call,
+                        &ctx.http,
+                        &ctx.data,
+                        guild_id,
+                        &QueryType::Keywords(keywords),
+                        requester,
+                    )
+                    .await
+                    {
+                        Ok(queue) => queue,
+                        Err(err) => match summary.record_failure(err) {
+                            Ok(false) => break,
+                            Ok(true) => continue,
+                            Err(err) => return Err(err),
+                        },
+                    };
                     update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
                 }
             }
@@ -285,20 +503,50 @@ pub async fn play(ctx: &Context, interaction: &mut CommandInteraction) -> Result
             let estimated_time = calculate_time_until_play(&queue, mode).await.unwrap();
 
             match (query_type, mode) {
-                (QueryType::VideoLink(_) | QueryType::Keywords(_), Mode::Next) => {
+                (
+                    QueryType::VideoLink(_)
+                    | QueryType::Keywords(_)
+                    | QueryType::File(_)
+                    | QueryType::Library(_)
+                    | QueryType::Radio(_),
+                    Mode::Next,
+                ) => {
                     let track = queue.get(1).unwrap();
                     let embed = create_queued_embed(PLAY_TOP, track, estimated_time).await;
 
                     edit_embed_response(&ctx.http, interaction, embed).await?;
                 }
-                (QueryType::VideoLink(_) | QueryType::Keywords(_), Mode::End) => {
+                (
+                    QueryType::VideoLink(_)
+                    | QueryType::Keywords(_)
+                    | QueryType::File(_)
+                    | QueryType::Library(_)
+                    | QueryType::Radio(_),
+                    Mode::End,
+                ) => {
                     let track = queue.last().unwrap();
                     let embed = create_queued_embed(PLAY_QUEUE, track, estimated_time).await;
 
                     edit_embed_response(&ctx.http, interaction, embed).await?;
                 }
                 (QueryType::PlaylistLink(_) | QueryType::KeywordList(_), _) => {
-                    edit_response(&ctx.http, interaction, ParrotMessage::PlaylistQueued).await?;
+                    let message = match summary {
+                        PlaylistSummary {
+                            limit_hit: None,
+                            duplicates: 0,
+                            blocked: 0,
+                        } => ParrotMessage::PlaylistQueued,
+                        PlaylistSummary {
+                            limit_hit,
+                            duplicates,
+                            blocked,
+                        } => ParrotMessage::PlaylistPartial {
+                            limit: limit_hit,
+                            duplicates,
+                            blocked,
+                        },
+                    };
+                    edit_response(&ctx.http, interaction, message).await?;
                 }
                 (_, _) => {}
             }
@@ -322,57 +570,50 @@ async fn calculate_time_until_play(queue: &[TrackHandle], mode: Mode) -> Option<
 
     let top_track = queue.first()?;
     let top_track_elapsed = top_track.get_info().await.unwrap().position;
-    let top_track_typemap_read_lock = top_track.typemap().read().await;
-    let top_track_aux_metadata = top_track_typemap_read_lock
-        .get::<AuxMetadataTypeMapKey>()
-        .unwrap();
 
-    let top_track_duration = match top_track_aux_metadata.duration {
+    let top_track_duration = match get_playback_duration(top_track).await {
         Some(duration) => duration,
         None => return Some(Duration::MAX),
     };
 
+    let top_track_remaining = top_track_duration.saturating_sub(top_track_elapsed);
+
     match mode {
-        Mode::Next => Some(top_track_duration - top_track_elapsed),
+        Mode::Next => Some(top_track_remaining),
         _ => {
             let center = &queue[1..queue.len() - 1];
-            let livestreams = center.len()
-                - center
-                    .iter()
-                    .filter_map(|t| {
-                        block_on(async {
-                            t.typemap()
-                                .read()
-                                .await
-                                .get::<AuxMetadataTypeMapKey>()
-                                .unwrap()
-                                .duration
-                        })
-                    })
-                    .count();
-
-            // if any of the tracks before are livestreams, the new track will never play
-            if livestreams > 0 {
-                return Some(Duration::MAX);
+            let mut durations = Duration::ZERO;
+
+            for track in center {
+                // if any of the tracks before are livestreams or radio, the new track will never play
+                let Some(duration) = get_playback_duration(track).await else {
+                    return Some(Duration::MAX);
+                };
+
+                durations += duration;
             }
 
-            let durations = center.iter().fold(Duration::ZERO, |acc, x| {
-                let duration = block_on(async {
-                    x.typemap()
-                        .read()
-                        .await
-                        .get::<AuxMetadataTypeMapKey>()
-                        .unwrap()
-                        .duration
-                });
-                acc + duration.unwrap()
-            });
-
-            Some(durations + top_track_duration - top_track_elapsed)
+            Some(durations + top_track_remaining)
         }
     }
 }
 
+/// How long a track takes to play through, accounting for its speed and offsets.
+async fn get_playback_duration(track: &TrackHandle) -> Option<Duration> {
+    let typemap = track.typemap().read().await;
+    let range = typemap
+        .get::<PlaybackRangeTypeMapKey>()
+        .copied()
+        .unwrap_or_default();
+
+    let end = range
+        .end
+        .or(typemap.get::<AuxMetadataTypeMapKey>()?.duration)?;
+    let duration = end.saturating_sub(range.start.unwrap_or_default());
+
+    Some(duration.div_f64(get_playback_tempo(&typemap)))
+}
+
 async fn create_queued_embed(
     title: &str,
     track: &TrackHandle,
@@ -385,7 +626,11 @@ async fn create_queued_embed(
         .unwrap()
         .clone();
 
-    embed = embed.thumbnail(metadata.thumbnail.unwrap()).field(
+    if let Some(thumbnail) = metadata.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+
+    embed = embed.field(
         title,
         format!(
             "[**{}**]({})",
@@ -395,18 +640,36 @@ async fn create_queued_embed(
         false,
     );
 
-    let footer_text = format!(
+    if let Some(requester) = track_typemap_read_lock.get::<RequesterTypeMapKey>() {
+        embed = embed.field(TRACK_REQUESTED_BY, requester.mention().to_string(), false);
+    }
+
+    let tempo = get_playback_tempo(&track_typemap_read_lock);
+    let duration = metadata.duration.map(|duration| duration.div_f64(tempo));
+
+    let mut footer_text = format!(
         "{}{}\n{}{}",
         TRACK_DURATION,
-        get_human_readable_timestamp(metadata.duration),
+        get_human_readable_timestamp(duration),
         TRACK_TIME_TO_PLAY,
         get_human_readable_timestamp(Some(estimated_time))
     );
 
+    if let Some(start) = track_typemap_read_lock
+        .get::<PlaybackRangeTypeMapKey>()
+        .and_then(|range| range.start)
+    {
+        footer_text.push_str(&format!(
+            "\n{}{}",
+            TRACK_START_OFFSET,
+            get_human_readable_timestamp(Some(start.div_f64(tempo)))
+        ));
+    }
+
     embed.footer(CreateEmbedFooter::new(footer_text))
 }
 
-fn get_track_source(query_type: QueryType) -> YoutubeDl {
+pub fn get_track_source(query_type: QueryType) -> YoutubeDl {
     let http_client = reqwest::Client::new();
 
     match query_type {
@@ -417,38 +680,345 @@ fn get_track_source(query_type: QueryType) -> YoutubeDl {
     }
 }
 
+/// Looks up what a single track query would play.
+pub async fn fetch_metadata(query_type: &QueryType) -> Result<AuxMetadata, ParrotError> {
+    match query_type {
+        QueryType::File(url) => AttachmentSource::fetch_metadata(url).await,
+        QueryType::Library(query) => Library::fetch_metadata(query).await,
+        QueryType::Radio(url) => RadioSource::fetch_metadata(url).await,
+        _ => get_track_source(query_type.clone())
+            .aux_metadata()
+            .await
+            .map_err(|_| ParrotError::Other("Unable to get AuxMetadata")),
+    }
+}
+
 async fn enqueue_track(
     call: &Arc<Mutex<Call>>,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
     query_type: &QueryType,
+    requester: UserId,
+) -> Result<Vec<TrackHandle>, ParrotError> {
+    append_track(call, http, data, guild_id, query_type, requester).await?;
+
+    let handler = call.lock().await;
+    apply_fair_queue(&handler, data, guild_id).await;
+
+    Ok(handler.queue().current_queue())
+}
+
+/// Adds a track to the back of the queue, regardless of the guild's fair queue setting.
+async fn append_track(
+    call: &Arc<Mutex<Call>>,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    query_type: &QueryType,
+    requester: UserId,
+) -> Result<Vec<TrackHandle>, ParrotError> {
+    let metadata = fetch_metadata(query_type).await?;
+
+    // the offsets only survive in the link, yt-dlp reports the plain video URL
+    let range = match query_type {
+        QueryType::VideoLink(url) => get_playback_range(url),
+        _ => PlaybackRange::default(),
+    };
+
+    let source_url = verify(
+        metadata.source_url.clone(),
+        ParrotError::Other("Unable to get the track's source URL"),
+    )?;
+    let track = PersistedTrack {
+        source_url,
+        metadata,
+        requester,
+        range,
+    };
+
+    let settings = get_playback_settings(data, guild_id).await;
+
+    let mut handler = call.lock().await;
+    check_queue_limits(&handler, data, guild_id, requester, &track.metadata).await?;
+
+    enqueue_with_settings(&mut handler, http, data, guild_id, track, &settings).await?;
+
+    Ok(handler.queue().current_queue())
+}
+
+pub async fn enqueue_persisted_track(
+    call: &Arc<Mutex<Call>>,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    track: &PersistedTrack,
 ) -> Result<Vec<TrackHandle>, ParrotError> {
-    let source = get_track_source(query_type.clone());
+    let settings = get_playback_settings(data, guild_id).await;
 
-    let mut source_c = source.clone();
+    // the metadata was already fetched when this track was first queued
     let mut handler = call.lock().await;
-    let track_handler = handler.enqueue_input(source.clone().into()).await;
+    enqueue_with_settings(&mut handler, http, data, guild_id, track.clone(), &settings).await?;
 
-    let mut track_handle_typemap = track_handler.typemap().write().await;
-    track_handle_typemap.insert::<AuxMetadataTypeMapKey>(
-        source_c
-            .aux_metadata()
-            .await
-            .map_err(|_| ParrotError::Other("Unable to get AuxMetadata"))?,
-    );
+    Ok(handler.queue().current_queue())
+}
+
+/// Recreates every queued track with the guild's current playback settings,
+/// resuming the one being played from where it was.
+pub async fn reload_queue(
+    call: &Arc<Mutex<Call>>,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+) -> Result<Vec<TrackHandle>, ParrotError> {
+    let settings = get_playback_settings(data, guild_id).await;
+
+    let mut handler = call.lock().await;
+    let old_tracks = handler.queue().current_queue();
+
+    let Some(top_track) = old_tracks.first() else {
+        return Ok(old_tracks);
+    };
+
+    let top_track_info = top_track.get_info().await.ok();
+    let top_track_typemap = top_track.typemap().read().await;
+    let old_tempo = get_playback_tempo(&top_track_typemap);
+    let loop_section = top_track_typemap.get::<LoopSectionTypeMapKey>().copied();
+    drop(top_track_typemap);
+
+    // everything the new tracks need is gathered before the queue is touched
+    let mut replacements = Vec::with_capacity(old_tracks.len());
+
+    for (i, old_track) in old_tracks.iter().enumerate() {
+        let mut replacement = verify(
+            PersistedTrack::from_track(old_track).await,
+            ParrotError::Other("Unable to get AuxMetadata"),
+        )?;
+
+        // the track being played is seeked to where it was instead
+        if i == 0 {
+            replacement.range.start = None;
+        }
+
+        replacements.push(replacement);
+    }
+
+    let mut new_tracks = Vec::with_capacity(old_tracks.len());
+
+    for replacement in replacements {
+        let new_track =
+            enqueue_with_settings(&mut handler, http, data, guild_id, replacement, &settings).await;
+
+        match new_track {
+            Ok(new_track) => new_tracks.push(new_track),
+            Err(err) => {
+                // the queue is left as it was rather than half reloaded
+                discard_tracks(&handler, &new_tracks).await;
+                return Err(err);
+            }
+        }
+    }
+
+    // lets the track end handler know the old tracks didn't actually finish
+    for old_track in old_tracks.iter() {
+        let mut old_track_typemap = old_track.typemap().write().await;
+        old_track_typemap.insert::<ReplacedTypeMapKey>(());
+    }
+
+    // drop the old tracks queued after the top one, leaving their replacements behind it
+    handler.queue().modify_queue(|queue| {
+        queue.drain(1..old_tracks.len());
+    });
+
+    for old_track in old_tracks.iter().skip(1) {
+        old_track.stop().ok();
+    }
+
+    force_skip_top_track(&handler).await?;
+
+    if let (Some(new_top_track), Some(info)) = (new_tracks.first(), top_track_info) {
+        let new_tempo = calculate_tempo(&settings.filters, &settings.rate);
+        let _ = new_top_track.seek(info.position.mul_f64(old_tempo / new_tempo));
+
+        if info.loops == LoopState::Infinite {
+            new_top_track.enable_loop().ok();
+        }
+
+        if info.playing == PlayMode::Pause {
+            new_top_track.pause().ok();
+        }
+
+        if let Some(section) = loop_section {
+            start_loop_section(new_top_track, section).await;
+        }
+    }
 
     Ok(handler.queue().current_queue())
 }
 
+/// Takes tracks back out of the queue without the track end handler treating them as played.
+async fn discard_tracks(handler: &Call, tracks: &[TrackHandle]) {
+    for track in tracks {
+        let mut typemap = track.typemap().write().await;
+        typemap.insert::<ReplacedTypeMapKey>(());
+    }
+
+    handler.queue().modify_queue(|queue| {
+        queue.retain(|queued| !tracks.iter().any(|track| track.uuid() == queued.uuid()));
+    });
+
+    for track in tracks {
+        track.stop().ok();
+    }
+}
+
+async fn enqueue_with_settings(
+    handler: &mut Call,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    track: PersistedTrack,
+    settings: &PlaybackSettings,
+) -> Result<TrackHandle, ParrotError> {
+    let PersistedTrack {
+        source_url: url,
+        metadata,
+        requester,
+        range,
+    } = track;
+
+    let filters = &settings.filters;
+    let skip_segments_video_id = get_youtube_video_id(&url).filter(|_| settings.sponsorblock);
+
+    // stations send song titles along, which are read whether or not ffmpeg filters the audio
+    let stream_title = RadioSource::is_playlist_url(&url).then(Arc::default);
+
+    let input: Input = match build_filter_graph(filters, &settings.rate, settings.normalize) {
+        Some(filter_graph) => FfmpegSource::new(url, filter_graph, metadata.clone())
+            .with_stream_title(stream_title.clone())
+            .into(),
+        None if Library::is_library_url(&url) => {
+            let path = verify(
+                Library::resolve(&url),
+                ParrotError::Other(FAIL_LIBRARY_DISABLED),
+            )?;
+            File::new(path).into()
+        }
+        None if RadioSource::is_playlist_url(&url) => {
+            RadioStream::new(url, stream_title.clone().unwrap_or_default()).into()
+        }
+        None if AttachmentSource::is_attachment_url(&url) => {
+            HttpRequest::new(reqwest::Client::new(), url).into()
+        }
+        None => get_track_source(QueryType::VideoLink(url)).into(),
+    };
+
+    // songbird would otherwise query yt-dlp for the duration all over again
+    let tempo = calculate_tempo(filters, &settings.rate);
+    let duration = range
+        .end
+        .or(metadata.duration)
+        .map(|duration| duration.div_f64(tempo));
+    let preload_time = duration.map(|duration| duration.saturating_sub(PRELOAD_TIME));
+
+    let mut track = Track::new(input).volume(settings.volume);
+    track.events.add_event(
+        EventData::new(Event::Track(TrackEvent::Play), ChaptersHandler),
+        Duration::ZERO,
+    );
+
+    // seeking a track before it plays would make songbird load it right away
+    if let Some(start) = range.start {
+        let start = start.div_f64(tempo);
+        track.events.add_event(
+            EventData::new(Event::Track(TrackEvent::Play), StartOffsetHandler { start }),
+            Duration::ZERO,
+        );
+    }
+
+    let track_handle = handler.enqueue_with_preload(track, preload_time);
+
+    // a timed event would drift after seeks, so the remaining time is checked periodically
+    if let Some(duration) = duration {
+        let crossfade_handler =
+            CrossfadeHandler::new(data.clone(), guild_id, handler.queue().clone(), duration);
+        track_handle
+            .add_event(
+                Event::Periodic(CROSSFADE_CHECK_INTERVAL, None),
+                crossfade_handler,
+            )
+            .ok();
+    }
+
+    // the station's song titles are read in the background, away from the track itself
+    if let Some(stream_title) = stream_title {
+        track_handle
+            .add_event(
+                Event::Periodic(STREAM_TITLE_CHECK_INTERVAL, None),
+                StreamTitleHandler {
+                    http: http.clone(),
+                    ctx_data: data.clone(),
+                    guild_id,
+                    queue: handler.queue().clone(),
+                    stream_title,
+                },
+            )
+            .ok();
+    }
+
+    if let Some(end) = range.end {
+        let end = end.div_f64(tempo);
+        track_handle
+            .add_event(
+                Event::Periodic(END_OFFSET_CHECK_INTERVAL, None),
+                EndOffsetHandler { end },
+            )
+            .ok();
+    }
+
+    // fetched in the background so enqueueing doesn't wait on the SponsorBlock API
+    if let Some(video_id) = skip_segments_video_id {
+        let track_handle = track_handle.clone();
+
+        tokio::spawn(async move {
+            match SponsorBlock::fetch_segments(&SPONSORBLOCK_API_URL, &video_id).await {
+                Ok(segments) if !segments.is_empty() => {
+                    let handler = SkipSegmentsHandler { segments, tempo };
+                    let event = Event::Periodic(SKIP_SEGMENTS_CHECK_INTERVAL, None);
+                    track_handle.add_event(event, handler).ok();
+                }
+                Ok(_) => {}
+                Err(err) => println!("{}", err),
+            }
+        });
+    }
+
+    let mut track_handle_typemap = track_handle.typemap().write().await;
+    track_handle_typemap.insert::<AuxMetadataTypeMapKey>(metadata);
+    track_handle_typemap.insert::<RequesterTypeMapKey>(requester);
+    track_handle_typemap.insert::<AudioFiltersTypeMapKey>(filters.clone());
+    track_handle_typemap.insert::<PlaybackRateTypeMapKey>(settings.rate);
+    track_handle_typemap.insert::<PlaybackRangeTypeMapKey>(range);
+    drop(track_handle_typemap);
+
+    Ok(track_handle)
+}
+
 async fn insert_track(
     call: &Arc<Mutex<Call>>,
+    http: &Arc<Http>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
     query_type: &QueryType,
     idx: usize,
+    requester: UserId,
 ) -> Result<Vec<TrackHandle>, ParrotError> {
     let handler = call.lock().await;
     let queue_size = handler.queue().len();
     drop(handler);
 
     if queue_size <= 1 {
-        let queue = enqueue_track(call, query_type).await?;
+        let queue = enqueue_track(call, http, data, guild_id, query_type, requester).await?;
         return Ok(queue);
     }
 
@@ -457,7 +1027,8 @@ async fn insert_track(
         ParrotError::NotInRange("index", idx as isize, 1, queue_size as isize),
     )?;
 
-    enqueue_track(call, query_type).await?;
+    // an explicit position takes precedence over taking turns
+    append_track(call, http, data, guild_id, query_type, requester).await?;
 
     let handler = call.lock().await;
     handler.queue().modify_queue(|queue| {
@@ -468,6 +1039,44 @@ async fn insert_track(
     Ok(handler.queue().current_queue())
 }
 
+/// The guild settings (and session playback rate) every newly enqueued track is created with.
+struct PlaybackSettings {
+    volume: f32,
+    filters: AudioFilters,
+    rate: PlaybackRate,
+    normalize: bool,
+    sponsorblock: bool,
+}
+
+async fn get_playback_settings(data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) -> PlaybackSettings {
+    let data = data.read().await;
+    let guild_settings = data
+        .get::<GuildSettingsMap>()
+        .and_then(|settings| settings.get(&guild_id));
+    let rate = data
+        .get::<GuildCacheMap>()
+        .and_then(|cache| cache.get(&guild_id))
+        .map(|cache| cache.playback_rate)
+        .unwrap_or_default();
+
+    match guild_settings {
+        Some(guild_settings) => PlaybackSettings {
+            volume: volume_to_gain(guild_settings.default_volume),
+            filters: guild_settings.filters.clone(),
+            rate,
+            normalize: guild_settings.normalize,
+            sponsorblock: guild_settings.sponsorblock,
+        },
+        None => PlaybackSettings {
+            volume: volume_to_gain(DEFAULT_VOLUME),
+            filters: AudioFilters::new(),
+            rate,
+            normalize: false,
+            sponsorblock: false,
+        },
+    }
+}
+
 async fn rotate_tracks(
     call: &Arc<Mutex<Call>>,
     n: usize,
@@ -479,15 +1088,78 @@ async fn rotate_tracks(
         ParrotError::Other("cannot rotate queues smaller than 3 tracks"),
     )?;
 
+    handler
+        .queue()
+        .modify_queue(|queue| rotate_upcoming(queue.make_contiguous(), n));
+
+    Ok(handler.queue().current_queue())
+}
+
+/// Moves the track just added to the back of the queue to its requester's next turn, if the
+/// guild has fair queueing on. Tracks already queued keep their places, so manual reordering
+/// isn't undone by later additions.
+async fn apply_fair_queue(handler: &Call, data: &Arc<RwLock<TypeMap>>, guild_id: GuildId) {
+    let fair_queue = data
+        .read()
+        .await
+        .get::<GuildSettingsMap>()
+        .and_then(|settings| settings.get(&guild_id))
+        .map_or(false, |settings| settings.fair_queue);
+
+    if !fair_queue {
+        return;
+    }
+
+    let queue = handler.queue().current_queue();
+    let mut requesters = Vec::with_capacity(queue.len());
+
+    for track in queue.iter() {
+        let typemap = track.typemap().read().await;
+        requesters.push(typemap.get::<RequesterTypeMapKey>().copied());
+    }
+
+    let Some((requester, queued)) = requesters.split_last() else {
+        return;
+    };
+    let slot = fair_slot(queued, requester);
+
+    // tracks that ended in the meantime only shorten the queue
     handler.queue().modify_queue(|queue| {
-        let mut not_playing = queue.split_off(1);
-        not_playing.rotate_right(n);
-        queue.append(&mut not_playing);
+        if slot < queue.len() {
+            let back = queue.pop_back().unwrap();
+            queue.insert(slot, back);
+        }
     });
+}
 
-    Ok(handler.queue().current_queue())
+/// Finds where a requester's new track goes so each requester's tracks take turns,
+/// without moving anything already queued. The first entry is the track being played,
+/// which stays put but counts as its requester's turn.
+pub fn fair_slot<T: PartialEq>(requesters: &[T], requester: &T) -> usize {
+    let turn = requesters.iter().filter(|r| *r == requester).count();
+    let mut slot = requesters.len().min(1);
+
+    // after the requester's own tracks and everyone's turns up to this one
+    for (i, queued) in requesters.iter().enumerate().skip(1) {
+        let queued_turn = requesters[..i].iter().filter(|r| *r == queued).count();
+
+        if queued == requester || queued_turn <= turn {
+            slot = i + 1;
+        }
+    }
+
+    slot
 }
 
+/// Rotates every track but the one being played `n` places to the right.
+pub fn rotate_upcoming<T>(queue: &mut [T], n: usize) {
+    if let Some(not_playing) = queue.get_mut(1..).filter(|tracks| !tracks.is_empty()) {
+        let len = not_playing.len();
+        not_playing.rotate_right(n % len);
+    }
+}
+
+/// Queues the query at the back of the queue, summarizing what was left out of it.
 pub async fn normal_query_type_resolver(
     call: &Arc<Mutex<Call>>,
     http: &Arc<Http>,
@@ -495,35 +1167,205 @@ pub async fn normal_query_type_resolver(
     guild_id: GuildId,
     query_type: &QueryType,
     mode: Mode,
-) -> Result<(), ParrotError> {
+    requester: UserId,
+) -> Result<PlaylistSummary, ParrotError> {
     match query_type.clone() {
-        QueryType::Keywords(_) | QueryType::VideoLink(_) => {
-            let queue = enqueue_track(call, query_type).await?;
+        QueryType::Keywords(_)
+        | QueryType::VideoLink(_)
+        | QueryType::File(_)
+        | QueryType::Library(_)
+        | QueryType::Radio(_) => {
+            let queue = enqueue_track(call, http, data, guild_id, query_type, requester).await?;
             update_queue_messages(http, data, &queue, guild_id).await;
-            Ok(())
+            Ok(PlaylistSummary::default())
         }
         QueryType::PlaylistLink(url) => {
-            let urls = get_urls_from_playlist(url, Some(mode)).await?;
+            let mut urls = get_urls_from_playlist(url, Some(mode)).await?;
+            let mut summary = PlaylistSummary {
+                limit_hit: limit_playlist(&mut urls, data, guild_id).await,
+                duplicates: 0,
+                blocked: 0,
+            };
 
             for url in urls.iter().filter_map(|v| v.clone()) {
-                let Ok(queue) = enqueue_track(call, &QueryType::VideoLink(url.to_string())).await
-                else {
-                    continue;
+                let queue = match enqueue_track(
+                    call,
+                    http,
+                    data,
+                    guild_id,
+                    &QueryType::VideoLink(url.to_string()),
+                    requester,
+                )
+                .await
+                {
+                    Ok(queue) => queue,
+                    Err(err) => match summary.record_failure(err) {
+                        Ok(false) => break,
+                        _ => continue,
+                    },
                 };
                 update_queue_messages(http, data, &queue, guild_id).await;
             }
-            Ok(())
+            Ok(summary)
         }
-        QueryType::KeywordList(keywords_list) => {
+        QueryType::KeywordList(mut keywords_list) => {
+            let mut summary = PlaylistSummary {
+                limit_hit: limit_playlist(&mut keywords_list, data, guild_id).await,
+                duplicates: 0,
+                blocked: 0,
+            };
+
             for keywords in keywords_list.iter() {
-                let queue = enqueue_track(call, &QueryType::Keywords(keywords.to_string())).await?;
+                let queue = match enqueue_track(
+                    call,
+                    http,
+                    data,
+                    guild_id,
+                    &QueryType::Keywords(keywords.to_string()),
+                    requester,
+                )
+                .await
+                {
+                    Ok(queue) => queue,
+                    Err(err) => match summary.record_failure(err) {
+                        Ok(false) => break,
+                        Ok(true) => continue,
+                        Err(err) => return Err(err),
+                    },
+                };
                 update_queue_messages(http, data, &queue, guild_id).await;
             }
-            Ok(())
+            Ok(summary)
         }
     }
 }
 
+/// The tracks of a playlist that were left out of the queue.
+#[derive(Default)]
+pub struct PlaylistSummary {
+    pub limit_hit: Option<QueueLimit>,
+    pub duplicates: usize,
+    /// Tracks from domains the guild doesn't allow, which were left out.
+    pub blocked: usize,
+}
+
+impl PlaylistSummary {
+    /// Counts a track that couldn't be queued, returning whether the rest of the
+    /// playlist should still be tried. Errors that aren't counted are handed back.
+    pub fn record_failure(&mut self, err: ParrotError) -> Result<bool, ParrotError> {
+        match err {
+            ParrotError::LimitReached(limit) => {
+                self.limit_hit = Some(limit);
+                Ok(false)
+            }
+            ParrotError::DuplicateTrack => {
+                self.duplicates += 1;
+                Ok(true)
+            }
+            err => Err(err),
+        }
+    }
+}
+
+/// Cuts a playlist down to the guild's import limit, returning the limit if it had to.
+pub async fn limit_playlist<T>(
+    items: &mut Vec<T>,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+) -> Option<QueueLimit> {
+    let max_playlist_size = data
+        .read()
+        .await
+        .get::<GuildSettingsMap>()?
+        .get(&guild_id)?
+        .max_playlist_size?;
+
+    if items.len() <= max_playlist_size {
+        return None;
+    }
+
+    items.truncate(max_playlist_size);
+    Some(QueueLimit::PlaylistSize(max_playlist_size))
+}
+
+/// Fails if queueing another track by `requester` would go over one of the guild's
+/// limits or against its duplicates policy.
+async fn check_queue_limits(
+    handler: &Call,
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    requester: UserId,
+    metadata: &AuxMetadata,
+) -> Result<(), ParrotError> {
+    let (max_queue_length, max_tracks_per_user, max_track_duration, duplicates) = data
+        .read()
+        .await
+        .get::<GuildSettingsMap>()
+        .and_then(|settings| settings.get(&guild_id))
+        .map(|settings| {
+            (
+                settings.max_queue_length,
+                settings.max_tracks_per_user,
+                settings.max_track_duration.map(Duration::from_secs),
+                settings.duplicates,
+            )
+        })
+        .unwrap_or_default();
+
+    // livestreams have no duration and are let through
+    if let (Some(max), Some(duration)) = (max_track_duration, metadata.duration) {
+        verify(
+            duration <= max,
+            ParrotError::LimitReached(QueueLimit::TrackDuration(max)),
+        )?;
+    }
+
+    let queue = handler.queue().current_queue();
+
+    if let Some(max) = max_queue_length {
+        verify(
+            queue.len() < max,
+            ParrotError::LimitReached(QueueLimit::QueueLength(max)),
+        )?;
+    }
+
+    if let Some(max) = max_tracks_per_user {
+        let mut queued = 0;
+
+        for track in queue.iter() {
+            let typemap = track.typemap().read().await;
+            if typemap.get::<RequesterTypeMapKey>() == Some(&requester) {
+                queued += 1;
+            }
+        }
+
+        verify(
+            queued < max,
+            ParrotError::LimitReached(QueueLimit::TracksPerUser(max)),
+        )?;
+    }
+
+    if let (DuplicatePolicy::Reject | DuplicatePolicy::RejectWithin(_), Some(url)) =
+        (duplicates, &metadata.source_url)
+    {
+        let mut queued_urls = Vec::with_capacity(queue.len());
+
+        for track in queue.iter() {
+            let typemap = track.typemap().read().await;
+            if let Some(metadata) = typemap.get::<AuxMetadataTypeMapKey>() {
+                queued_urls.extend(metadata.source_url.clone());
+            }
+        }
+
+        verify(
+            !duplicates.rejects(&queued_urls, url),
+            ParrotError::DuplicateTrack,
+        )?;
+    }
+
+    Ok(())
+}
+
 pub async fn get_urls_from_playlist(
     url: String,
     mode: Option<Mode>,
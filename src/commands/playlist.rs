@@ -0,0 +1,366 @@
+use crate::{
+    commands::{
+        play::{
+            fetch_metadata, is_domain_allowed, normal_query_type_resolver, parse_query, Mode,
+            ParsedQuery, PlaylistSummary, QueryType,
+        },
+        summon::summon,
+    },
+    errors::{verify, ParrotError},
+    guild::{
+        persisted_queue::PersistedTrack,
+        playlists::{PlaylistScope, SavedPlaylists},
+        stored_queue::store_query,
+    },
+    messaging::{
+        message::ParrotMessage,
+        messages::{
+            FAIL_PLAYLIST_NOT_FOUND, FAIL_PLAYLIST_NOT_TRACK, FAIL_PLAYLIST_PERMISSION,
+            PLAYLISTS_EMPTY, PLAYLISTS_GUILD_TITLE, PLAYLISTS_USER_TITLE, PLAYLIST_TRACKS,
+        },
+    },
+    sources::library::Library,
+    utils::{create_embed_response, create_response, edit_response},
+};
+use serenity::{
+    all::{CommandDataOption, CommandDataOptionValue, CommandInteraction},
+    builder::CreateEmbed,
+    client::Context,
+    model::id::GuildId,
+    prelude::{RwLock, TypeMap},
+};
+use std::sync::Arc;
+use url::Url;
+
+/// Embed fields can't hold more than this many characters.
+const EMBED_FIELD_LIMIT: usize = 1024;
+
+pub async fn playlist(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let args = interaction.data.options.clone();
+    let subcommand = args.first().unwrap();
+
+    let CommandDataOptionValue::SubCommand(options) = &subcommand.value else {
+        unreachable!()
+    };
+
+    let scope = match get_option(options, "scope") {
+        Some("server") => PlaylistScope::Guild(guild_id),
+        _ => PlaylistScope::User(interaction.user.id),
+    };
+    let name = get_option(options, "name").map(|name| name.trim().to_string());
+
+    match subcommand.name.as_str() {
+        "add" => {
+            let query = get_option(options, "query").map(ToOwned::to_owned);
+            add_to_playlist(ctx, interaction, scope, name.unwrap(), query).await
+        }
+        "delete" => delete_playlist(ctx, interaction, scope, name.unwrap()).await,
+        "list" => list_playlists(ctx, interaction).await,
+        "load" => load_playlist(ctx, interaction, scope, name.unwrap()).await,
+        "save" => save_playlist(ctx, interaction, scope, name.unwrap()).await,
+        _ => unreachable!(),
+    }
+}
+
+async fn save_playlist(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    scope: PlaylistScope,
+    name: String,
+) -> Result<(), ParrotError> {
+    verify_can_edit(interaction, scope)?;
+
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).ok_or(ParrotError::NothingPlaying)?;
+
+    let queue = call.lock().await.queue().current_queue();
+    let mut tracks = Vec::with_capacity(queue.len());
+
+    for track in queue.iter() {
+        if let Some(track) = PersistedTrack::from_track(track).await {
+            tracks.push(track);
+        }
+    }
+    verify(!tracks.is_empty(), ParrotError::NothingPlaying)?;
+
+    let count = tracks.len();
+    let mut saved = SavedPlaylists::load(scope)?;
+    saved.playlists.insert(name.clone(), tracks);
+    saved.save(scope)?;
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::PlaylistSaved { name, count },
+    )
+    .await
+}
+
+async fn load_playlist(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    scope: PlaylistScope,
+    name: String,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let requester = interaction.user.id;
+
+    let saved = SavedPlaylists::load(scope)?;
+    let tracks = saved
+        .playlists
+        .get(&name)
+        .ok_or(ParrotError::Other(FAIL_PLAYLIST_NOT_FOUND))?;
+
+    summon(ctx, interaction, false).await?;
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    create_response(&ctx.http, interaction, ParrotMessage::Search).await?;
+
+    let mut summary = PlaylistSummary::default();
+    let mut count = 0;
+
+    for track in tracks {
+        // the playlist may have been saved before the domain was banned, or in another server
+        let Some(query_type) = get_query_type(&ctx.data, guild_id, track).await else {
+            summary.blocked += 1;
+            continue;
+        };
+
+        match normal_query_type_resolver(
+            &call,
+            &ctx.http,
+            &ctx.data,
+            guild_id,
+            &query_type,
+            Mode::End,
+            requester,
+        )
+        .await
+        {
+            // tracks that couldn't be queued shouldn't come back when the queue loops
+            Ok(_) => {
+                store_query(&ctx.data, guild_id, query_type, requester).await;
+                count += 1;
+            }
+            Err(err) => match summary.record_failure(err) {
+                Ok(false) => break,
+                _ => continue,
+            },
+        }
+    }
+
+    let message = match summary {
+        PlaylistSummary {
+            limit_hit: None,
+            duplicates: 0,
+            blocked: 0,
+        } => ParrotMessage::PlaylistLoaded { name, count },
+        PlaylistSummary {
+            limit_hit,
+            duplicates,
+            blocked,
+        } => ParrotMessage::PlaylistPartial {
+            limit: limit_hit,
+            duplicates,
+            blocked,
+        },
+    };
+
+    edit_response(&ctx.http, interaction, message).await?;
+    Ok(())
+}
+
+async fn list_playlists(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+
+    let user_playlists = SavedPlaylists::load(PlaylistScope::User(interaction.user.id))?;
+    let guild_playlists = SavedPlaylists::load(PlaylistScope::Guild(guild_id))?;
+
+    let embed = CreateEmbed::new()
+        .field(
+            PLAYLISTS_USER_TITLE,
+            build_playlists_field(&user_playlists),
+            false,
+        )
+        .field(
+            PLAYLISTS_GUILD_TITLE,
+            build_playlists_field(&guild_playlists),
+            false,
+        );
+
+    create_embed_response(&ctx.http, interaction, embed).await
+}
+
+async fn delete_playlist(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    scope: PlaylistScope,
+    name: String,
+) -> Result<(), ParrotError> {
+    verify_can_edit(interaction, scope)?;
+
+    let mut saved = SavedPlaylists::load(scope)?;
+    let removed = saved.playlists.remove(&name);
+    verify(removed, ParrotError::Other(FAIL_PLAYLIST_NOT_FOUND))?;
+    saved.save(scope)?;
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::PlaylistDeleted { name },
+    )
+    .await
+}
+
+async fn add_to_playlist(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    scope: PlaylistScope,
+    name: String,
+    query: Option<String>,
+) -> Result<(), ParrotError> {
+    verify_can_edit(interaction, scope)?;
+
+    // fetching the metadata takes longer than interactions can wait for
+    create_response(&ctx.http, interaction, ParrotMessage::Search).await?;
+
+    let track = match query {
+        Some(query) => {
+            let guild_id = interaction.guild_id.unwrap();
+
+            // the library isn't on the internet, so the domain rules don't apply to it
+            let query_type = if Library::is_library_url(&query) {
+                QueryType::Library(query)
+            } else {
+                match parse_query(&ctx.data, guild_id, &query).await? {
+                    ParsedQuery::Allowed(query_type) => query_type,
+                    ParsedQuery::BlockedDomain(domain) => {
+                        return create_response(
+                            &ctx.http,
+                            interaction,
+                            ParrotMessage::PlayDomainBanned { domain },
+                        )
+                        .await;
+                    }
+                }
+            };
+
+            if let QueryType::PlaylistLink(_) | QueryType::KeywordList(_) = query_type {
+                return Err(ParrotError::Other(FAIL_PLAYLIST_NOT_TRACK));
+            }
+
+            let metadata = fetch_metadata(&query_type).await?;
+
+            PersistedTrack {
+                source_url: verify(
+                    metadata.source_url.clone(),
+                    ParrotError::Other("Unable to get AuxMetadata"),
+                )?,
+                metadata,
+                requester: interaction.user.id,
+                range: Default::default(),
+            }
+        }
+        None => {
+            let guild_id = interaction.guild_id.unwrap();
+            let manager = songbird::get(ctx).await.unwrap();
+            let call = manager.get(guild_id).ok_or(ParrotError::NothingPlaying)?;
+
+            let track = call.lock().await.queue().current();
+            let track = track.ok_or(ParrotError::NothingPlaying)?;
+            verify(
+                PersistedTrack::from_track(&track).await,
+                ParrotError::NothingPlaying,
+            )?
+        }
+    };
+
+    let title = track.metadata.title.clone().unwrap_or_default();
+    let url = track.source_url.clone();
+
+    let mut saved = SavedPlaylists::load(scope)?;
+    saved.playlists.entry(name.clone()).or_default().push(track);
+    saved.save(scope)?;
+
+    edit_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::PlaylistTrackAdded { title, url, name },
+    )
+    .await?;
+    Ok(())
+}
+
+/// The query that queues a saved track again, or `None` if the guild blocks where it's from.
+async fn get_query_type(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    track: &PersistedTrack,
+) -> Option<QueryType> {
+    let query_type = QueryType::from_source_url(track.source_url.clone());
+
+    // uploads and the library don't come from the internet, so the domain rules don't apply
+    if let QueryType::File(_) | QueryType::Library(_) = query_type {
+        return Some(query_type);
+    }
+
+    let url = Url::parse(&track.source_url).ok()?;
+    let domain = url.host_str()?;
+
+    is_domain_allowed(data, guild_id, domain)
+        .await
+        .then_some(query_type)
+}
+
+/// Only members who can manage the server may change its shared playlists.
+fn verify_can_edit(
+    interaction: &CommandInteraction,
+    scope: PlaylistScope,
+) -> Result<(), ParrotError> {
+    let can_edit = match scope {
+        PlaylistScope::User(_) => true,
+        PlaylistScope::Guild(_) => interaction
+            .member
+            .as_ref()
+            .and_then(|member| member.permissions)
+            .map_or(false, |permissions| permissions.manage_guild()),
+    };
+
+    verify(can_edit, ParrotError::Other(FAIL_PLAYLIST_PERMISSION))?;
+    Ok(())
+}
+
+fn get_option<'a>(options: &'a [CommandDataOption], name: &str) -> Option<&'a str> {
+    options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.value.as_str())
+}
+
+fn build_playlists_field(saved: &SavedPlaylists) -> String {
+    if saved.playlists.is_empty() {
+        return String::from(PLAYLISTS_EMPTY);
+    }
+
+    let mut field = String::new();
+
+    for (name, tracks) in saved.playlists.iter() {
+        let line = format!("`{}` • {} {}\n", name, tracks.len(), PLAYLIST_TRACKS);
+        if field.len() + line.len() > EMBED_FIELD_LIMIT {
+            break;
+        }
+        field.push_str(&line);
+    }
+
+    field
+}
@@ -0,0 +1,85 @@
+use crate::{
+    commands::{play::enqueue_persisted_track, skip::force_skip_top_track},
+    errors::{verify, ParrotError},
+    guild::{cache::GuildCacheMap, persisted_queue::PersistedTrack},
+    handlers::track_end::update_queue_messages,
+    messaging::{message::ParrotMessage, messages::FAIL_NO_PREVIOUS_TRACK},
+    utils::{create_response, ReplacedTypeMapKey},
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn previous(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let mut data = ctx.data.write().await;
+    let previous_track = data
+        .get_mut::<GuildCacheMap>()
+        .unwrap()
+        .get_mut(&guild_id)
+        .and_then(|cache| cache.history.pop_back());
+    drop(data);
+
+    let previous_track = verify(previous_track, ParrotError::Other(FAIL_NO_PREVIOUS_TRACK))?;
+
+    let handler = call.lock().await;
+    let current_track = handler.queue().current();
+    drop(handler);
+
+    // the current track starts over once the previous one is done playing
+    let current_track_copy = match &current_track {
+        Some(track) => PersistedTrack::from_track(track).await,
+        None => None,
+    };
+
+    enqueue_persisted_track(&call, &ctx.http, &ctx.data, guild_id, &previous_track).await?;
+
+    if let Some(track) = &current_track_copy {
+        enqueue_persisted_track(&call, &ctx.http, &ctx.data, guild_id, track).await?;
+    }
+
+    let handler = call.lock().await;
+
+    if let Some(current_track) = current_track {
+        // keeps the current track from being recorded as played
+        if current_track_copy.is_some() {
+            current_track
+                .typemap()
+                .write()
+                .await
+                .insert::<ReplacedTypeMapKey>(());
+        }
+
+        let num_added = if current_track_copy.is_some() { 2 } else { 1 };
+
+        handler.queue().modify_queue(|queue| {
+            let added: Vec<_> = queue.drain(queue.len() - num_added..).collect();
+
+            for (offset, track) in added.into_iter().enumerate() {
+                queue.insert(offset + 1, track);
+            }
+        });
+
+        force_skip_top_track(&handler).await?;
+    }
+
+    let queue = handler.queue().current_queue();
+    drop(handler);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::Previous {
+            title: previous_track.metadata.title.unwrap_or_default(),
+            url: previous_track.source_url,
+        },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::{
+    commands::queue_file::{export_queue, import_queue, QueueFileFormat},
     errors::ParrotError,
     guild::cache::GuildCacheMap,
     handlers::track_end::ModifyQueueHandler,
@@ -6,18 +7,19 @@ use crate::{
         QUEUE_EXPIRED, QUEUE_NOTHING_IS_PLAYING, QUEUE_NOW_PLAYING, QUEUE_NO_SONGS, QUEUE_PAGE,
         QUEUE_PAGE_OF, QUEUE_UP_NEXT,
     },
-    utils::{get_human_readable_timestamp, AuxMetadataTypeMapKey},
+    utils::{get_human_readable_timestamp, AuxMetadataTypeMapKey, RequesterTypeMapKey},
 };
 use serenity::{
     all::{
-        ButtonStyle, CommandInteraction, CreateActionRow, CreateEmbedFooter,
-        CreateInteractionResponse, CreateInteractionResponseMessage, EditMessage,
+        ButtonStyle, CommandDataOptionValue, CommandInteraction, CreateActionRow,
+        CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+        EditMessage,
     },
     builder::{CreateButton, CreateEmbed},
     client::Context,
     futures::StreamExt,
     model::{channel::Message, id::GuildId},
-    prelude::{RwLock, TypeMap},
+    prelude::{Mentionable, RwLock, TypeMap},
 };
 use songbird::{tracks::TrackHandle, Event, TrackEvent};
 use std::{
@@ -28,10 +30,43 @@ use std::{
     time::Duration,
 };
 
-const EMBED_PAGE_SIZE: usize = 6;
-const EMBED_TIMEOUT: u64 = 3600;
+pub const EMBED_PAGE_SIZE: usize = 6;
+pub const EMBED_TIMEOUT: u64 = 3600;
 
 pub async fn queue(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let args = interaction.data.options.clone();
+    let subcommand = args.first().unwrap();
+
+    let CommandDataOptionValue::SubCommand(options) = &subcommand.value else {
+        unreachable!()
+    };
+
+    match subcommand.name.as_str() {
+        "export" => {
+            let format = options
+                .iter()
+                .find(|option| option.name == "format")
+                .and_then(|option| option.value.as_str())
+                .and_then(QueueFileFormat::from_name)
+                .unwrap_or_default();
+            export_queue(ctx, interaction, format).await
+        }
+        "import" => {
+            let attachment_id = options
+                .iter()
+                .find(|option| option.name == "file")
+                .and_then(|option| option.value.as_attachment_id())
+                .unwrap();
+            import_queue(ctx, interaction, attachment_id).await
+        }
+        _ => show_queue(ctx, interaction).await,
+    }
+}
+
+async fn show_queue(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
     let guild_id = interaction.guild_id.unwrap();
     let manager = songbird::get(ctx).await.unwrap();
     let call = manager.get(guild_id).unwrap();
@@ -91,12 +126,9 @@ pub async fn queue(ctx: &Context, interaction: &mut CommandInteraction) -> Resul
         let num_pages = calculate_num_pages(&tracks);
         let mut page_wlock = page.write().await;
 
-        *page_wlock = match btn_id.as_str() {
-            "<<" => 0,
-            "<" => min(page_wlock.saturating_sub(1), num_pages - 1),
-            ">" => min(page_wlock.add(1), num_pages - 1),
-            ">>" => num_pages - 1,
-            _ => continue,
+        *page_wlock = match turn_page(btn_id, *page_wlock, num_pages) {
+            Some(page) => page,
+            None => continue,
         };
 
         let message = build_nav_btns(
@@ -134,13 +166,16 @@ pub async fn create_queue_embed(tracks: &[TrackHandle], page: usize) -> CreateEm
             .get::<AuxMetadataTypeMapKey>()
             .unwrap()
             .clone();
-        embed = embed.thumbnail(metadata.thumbnail.unwrap());
+        if let Some(thumbnail) = metadata.thumbnail.clone() {
+            embed = embed.thumbnail(thumbnail);
+        }
 
         format!(
-            "[{}]({}) • `{}`",
+            "[{}]({}) • `{}`{}",
             metadata.title.as_ref().unwrap(),
             metadata.source_url.as_ref().unwrap(),
-            get_human_readable_timestamp(metadata.duration)
+            get_human_readable_timestamp(metadata.duration),
+            format_requester(&first_track_typemap_read_lock)
         )
     } else {
         String::from(QUEUE_NOTHING_IS_PLAYING)
@@ -211,22 +246,50 @@ async fn build_queue_page(tracks: &[TrackHandle], page: usize) -> String {
 
         let _ = writeln!(
             description,
-            "`{}.` [{}]({}) • `{}`",
+            "`{}.` [{}]({}) • `{}`{}",
             i + start_idx + 1,
             title,
             url,
-            duration
+            duration,
+            format_requester(&track_typemap_read_lock)
         );
     }
 
     description
 }
 
+/// Mentions who asked for a track, if known. Mentions in embeds don't ping anyone.
+fn format_requester(typemap: &TypeMap) -> String {
+    typemap
+        .get::<RequesterTypeMapKey>()
+        .map(|requester| format!(" • {}", requester.mention()))
+        .unwrap_or_default()
+}
+
 pub fn calculate_num_pages(tracks: &[TrackHandle]) -> usize {
-    let num_pages = ((tracks.len() as f64 - 1.0) / EMBED_PAGE_SIZE as f64).ceil() as usize;
+    // the first track is the one playing, which isn't listed on the pages
+    count_pages(tracks.len().saturating_sub(1))
+}
+
+/// How many embed pages it takes to list this many items, at least one.
+pub fn count_pages(num_items: usize) -> usize {
+    let num_pages = (num_items as f64 / EMBED_PAGE_SIZE as f64).ceil() as usize;
     max(1, num_pages)
 }
 
+/// The page a navigation button leads to, or `None` if it isn't one of ours.
+pub fn turn_page(btn_id: &str, page: usize, num_pages: usize) -> Option<usize> {
+    let last_page = num_pages.saturating_sub(1);
+
+    match btn_id {
+        "<<" => Some(0),
+        "<" => Some(min(page.saturating_sub(1), last_page)),
+        ">" => Some(min(page.add(1), last_page)),
+        ">>" => Some(last_page),
+        _ => None,
+    }
+}
+
 pub async fn forget_queue_message(
     data: &Arc<RwLock<TypeMap>>,
     message: &mut Message,
@@ -0,0 +1,253 @@
+use crate::{
+    commands::{
+        play::{
+            limit_playlist, normal_query_type_resolver, parse_query, Mode, ParsedQuery,
+            PlaylistSummary, QueryType,
+        },
+        summon::summon,
+    },
+    errors::{verify, ParrotError},
+    guild::{persisted_queue::PersistedTrack, stored_queue::store_query},
+    messaging::{
+        message::ParrotMessage,
+        messages::{FAIL_QUEUE_IMPORT_EMPTY, FAIL_QUEUE_IMPORT_TOO_LARGE},
+    },
+    sources::library::Library,
+    utils::{create_response, edit_response},
+};
+use serenity::{
+    all::{
+        AttachmentId, CommandInteraction, CreateAttachment, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    builder::CreateEmbed,
+    client::Context,
+    model::id::GuildId,
+    prelude::{RwLock, TypeMap},
+};
+use std::{fmt::Write, sync::Arc};
+
+/// Largest attachment that will be read when importing a queue.
+const MAX_IMPORT_SIZE: u32 = 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum QueueFileFormat {
+    #[default]
+    M3u,
+    Json,
+    Txt,
+}
+
+impl QueueFileFormat {
+    pub fn from_name(name: &str) -> Option<QueueFileFormat> {
+        match name {
+            "m3u" => Some(Self::M3u),
+            "json" => Some(Self::Json),
+            "txt" => Some(Self::Txt),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::M3u => "m3u",
+            Self::Json => "json",
+            Self::Txt => "txt",
+        }
+    }
+}
+
+pub async fn export_queue(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    format: QueueFileFormat,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let queue = call.lock().await.queue().current_queue();
+    let mut tracks = Vec::with_capacity(queue.len());
+
+    for track in queue.iter() {
+        if let Some(track) = PersistedTrack::from_track(track).await {
+            tracks.push(track);
+        }
+    }
+    verify(!tracks.is_empty(), ParrotError::QueueEmpty)?;
+
+    let contents = format_queue_file(&tracks, format)?;
+    let message = ParrotMessage::QueueExported {
+        count: tracks.len(),
+    };
+
+    let response = CreateInteractionResponseMessage::new()
+        .add_embed(CreateEmbed::new().description(format!("{message}")))
+        .add_file(CreateAttachment::bytes(
+            contents.into_bytes(),
+            format!("queue.{}", format.extension()),
+        ));
+
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn import_queue(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    attachment_id: AttachmentId,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let requester = interaction.user.id;
+
+    let attachment = interaction
+        .data
+        .resolved
+        .attachments
+        .get(&attachment_id)
+        .unwrap()
+        .clone();
+    verify(
+        attachment.size <= MAX_IMPORT_SIZE,
+        ParrotError::Other(FAIL_QUEUE_IMPORT_TOO_LARGE),
+    )?;
+
+    let contents = attachment.download().await?;
+    let mut entries = parse_queue_file(&String::from_utf8_lossy(&contents));
+    verify(
+        !entries.is_empty(),
+        ParrotError::Other(FAIL_QUEUE_IMPORT_EMPTY),
+    )?;
+
+    summon(ctx, interaction, false).await?;
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    // importing an entire queue takes longer than interactions can wait for
+    create_response(&ctx.http, interaction, ParrotMessage::Search).await?;
+
+    let queue_len = call.lock().await.queue().len();
+    let mut summary = PlaylistSummary {
+        limit_hit: limit_playlist(&mut entries, &ctx.data, guild_id).await,
+        duplicates: 0,
+        blocked: 0,
+    };
+    let mut failed = 0;
+
+    for entry in entries {
+        let Some(query_type) = get_query_type(&ctx.data, guild_id, &entry).await else {
+            failed += 1;
+            continue;
+        };
+
+        match normal_query_type_resolver(
+            &call,
+            &ctx.http,
+            &ctx.data,
+            guild_id,
+            &query_type,
+            Mode::End,
+            requester,
+        )
+        .await
+        {
+            // entries that couldn't be queued shouldn't come back when the queue loops
+            Ok(entry_summary) => {
+                store_query(&ctx.data, guild_id, query_type, requester).await;
+                summary.duplicates += entry_summary.duplicates;
+
+                if entry_summary.limit_hit.is_some() {
+                    summary.limit_hit = entry_summary.limit_hit;
+                    break;
+                }
+            }
+            Err(err) => match summary.record_failure(err) {
+                Ok(false) => break,
+                Ok(true) => {}
+                Err(_) => failed += 1,
+            },
+        }
+    }
+
+    let count = call.lock().await.queue().len().saturating_sub(queue_len);
+
+    edit_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::QueueImported {
+            count,
+            skipped: failed + summary.duplicates,
+            limit: summary.limit_hit,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Writes the tracks out in the given format, one entry per track.
+pub fn format_queue_file(
+    tracks: &[PersistedTrack],
+    format: QueueFileFormat,
+) -> Result<String, ParrotError> {
+    let mut contents = String::new();
+
+    match format {
+        QueueFileFormat::M3u => {
+            contents.push_str("#EXTM3U\n");
+
+            for track in tracks {
+                let seconds = track
+                    .metadata
+                    .duration
+                    .map_or(-1, |duration| duration.as_secs() as i64);
+                let title = track.metadata.title.as_deref().unwrap_or_default();
+
+                let _ = writeln!(contents, "#EXTINF:{},{}", seconds, title);
+                let _ = writeln!(contents, "{}", track.source_url);
+            }
+        }
+        QueueFileFormat::Json => contents = serde_json::to_string_pretty(tracks)?,
+        QueueFileFormat::Txt => {
+            for track in tracks {
+                let _ = writeln!(contents, "{}", track.source_url);
+            }
+        }
+    }
+
+    Ok(contents)
+}
+
+/// Reads the entries back out of an exported queue, either links or search terms.
+pub fn parse_queue_file(contents: &str) -> Vec<String> {
+    if let Ok(tracks) = serde_json::from_str::<Vec<PersistedTrack>>(contents) {
+        return tracks.into_iter().map(|track| track.source_url).collect();
+    }
+
+    // plain lists and M3U playlists only differ by the latter's comment lines
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// Resolves an entry the same way /play would, or `None` if it can't be queued.
+async fn get_query_type(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    entry: &str,
+) -> Option<QueryType> {
+    if Library::is_library_url(entry) {
+        return Some(QueryType::Library(entry.to_string()));
+    }
+
+    match parse_query(data, guild_id, entry).await {
+        Ok(ParsedQuery::Allowed(query_type)) => Some(query_type),
+        _ => None,
+    }
+}
@@ -5,9 +5,15 @@ use crate::{
     messaging::{message::ParrotMessage, messages::REMOVED_QUEUE},
     utils::{create_embed_response, create_response, AuxMetadataTypeMapKey},
 };
-use serenity::{all::CommandInteraction, builder::CreateEmbed, client::Context};
-use songbird::tracks::TrackHandle;
-use std::{cmp::min, convert::TryInto};
+use serenity::{
+    all::CommandInteraction,
+    builder::CreateEmbed,
+    client::Context,
+    model::id::GuildId,
+    prelude::{RwLock, TypeMap},
+};
+use songbird::{tracks::TrackHandle, Call};
+use std::{cmp::min, convert::TryInto, sync::Arc};
 
 pub async fn remove(
     ctx: &Context,
@@ -86,15 +92,55 @@ async fn create_remove_enqueued_embed(track: &TrackHandle) -> CreateEmbed {
         .unwrap()
         .clone();
 
-    embed
-        .field(
-            REMOVED_QUEUE,
-            format!(
-                "[**{}**]({})",
-                metadata.title.unwrap(),
-                metadata.source_url.unwrap()
-            ),
-            false,
-        )
-        .thumbnail(metadata.thumbnail.unwrap())
+    let embed = embed.field(
+        REMOVED_QUEUE,
+        format!(
+            "[**{}**]({})",
+            metadata.title.unwrap(),
+            metadata.source_url.unwrap()
+        ),
+        false,
+    );
+
+    match metadata.thumbnail {
+        Some(thumbnail) => embed.thumbnail(thumbnail),
+        None => embed,
+    }
+}
+
+/// Drops the given tracks from the queue, wherever they are in it, and returns the
+/// refreshed queue.
+pub fn remove_tracks(handler: &Call, tracks: &[TrackHandle]) -> Vec<TrackHandle> {
+    handler.queue().modify_queue(|queue| {
+        queue.retain(|queued| !tracks.iter().any(|track| track.uuid() == queued.uuid()));
+    });
+
+    for track in tracks {
+        track.stop().ok();
+    }
+
+    handler.queue().current_queue()
+}
+
+/// Drops the entries at the given queue positions from the guild's stored queue, which
+/// follows the songbird queue entry for entry.
+pub async fn remove_stored_entries(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    positions: &[usize],
+) {
+    let mut data = data.write().await;
+    let Some(guild_stored_queue) = data
+        .get_mut::<GuildStoredQueueMap>()
+        .and_then(|stored_queues| stored_queues.get_mut(&guild_id))
+    else {
+        return;
+    };
+
+    let mut position = 0;
+    guild_stored_queue.queue.retain(|_| {
+        let keep = !positions.contains(&position);
+        position += 1;
+        keep
+    });
 }
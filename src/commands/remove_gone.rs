@@ -0,0 +1,62 @@
+use crate::{
+    commands::remove::{remove_stored_entries, remove_tracks},
+    connection::get_voice_channel_for_user,
+    errors::ParrotError,
+    handlers::track_end::update_queue_messages,
+    messaging::message::ParrotMessage,
+    utils::{create_response, RequesterTypeMapKey},
+};
+use serenity::{all::CommandInteraction, client::Context, model::id::UserId};
+use std::collections::HashSet;
+
+pub async fn remove_gone(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let listeners: HashSet<UserId> = {
+        let guild = ctx.cache.guild(guild_id).unwrap();
+        let bot_channel_id = get_voice_channel_for_user(&guild, &ctx.cache.current_user().id);
+
+        guild
+            .voice_states
+            .values()
+            .filter(|state| state.channel_id.is_some() && state.channel_id == bot_channel_id)
+            .map(|state| state.user_id)
+            .collect()
+    };
+
+    let handler = call.lock().await;
+    let queue = handler.queue().current_queue();
+
+    // the track being played is left alone, it can be skipped instead
+    let mut gone = Vec::new();
+    let mut positions = Vec::new();
+    for (position, track) in queue.iter().enumerate().skip(1) {
+        let typemap = track.typemap().read().await;
+        if let Some(requester) = typemap.get::<RequesterTypeMapKey>() {
+            if !listeners.contains(requester) {
+                gone.push(track.clone());
+                positions.push(position);
+            }
+        }
+    }
+
+    let queue = remove_tracks(&handler, &gone);
+    drop(handler);
+
+    remove_stored_entries(&ctx.data, guild_id, &positions).await;
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::RemoveGone { count: gone.len() },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
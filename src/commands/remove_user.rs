@@ -0,0 +1,52 @@
+use crate::{
+    commands::remove::{remove_stored_entries, remove_tracks},
+    errors::ParrotError,
+    handlers::track_end::update_queue_messages,
+    messaging::message::ParrotMessage,
+    utils::{create_response, RequesterTypeMapKey},
+};
+use serenity::{all::CommandInteraction, client::Context, prelude::Mentionable};
+
+pub async fn remove_user(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let args = interaction.data.options.clone();
+    let user_id = args.first().unwrap().value.as_user_id().unwrap();
+
+    let handler = call.lock().await;
+    let queue = handler.queue().current_queue();
+
+    // the track being played is left alone, it can be skipped instead
+    let mut requested = Vec::new();
+    let mut positions = Vec::new();
+    for (position, track) in queue.iter().enumerate().skip(1) {
+        let typemap = track.typemap().read().await;
+        if typemap.get::<RequesterTypeMapKey>() == Some(&user_id) {
+            requested.push(track.clone());
+            positions.push(position);
+        }
+    }
+
+    let queue = remove_tracks(&handler, &requested);
+    drop(handler);
+
+    remove_stored_entries(&ctx.data, guild_id, &positions).await;
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::RemoveUser {
+            count: requested.len(),
+            mention: user_id.mention(),
+        },
+    )
+    .await?;
+
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
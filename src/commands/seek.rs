@@ -1,30 +1,34 @@
 use crate::{
     errors::{verify, ParrotError},
     messaging::message::ParrotMessage,
-    messaging::messages::{FAIL_MINUTES_PARSING, FAIL_SECONDS_PARSING},
-    utils::create_response,
+    messaging::messages::{FAIL_INVALID_TIMESTAMP, FAIL_SEEK_OUT_OF_BOUNDS},
+    utils::{
+        create_response, get_human_readable_timestamp, get_playback_tempo, parse_seek_target,
+        AuxMetadataTypeMapKey, SeekTarget,
+    },
 };
 use serenity::{all::CommandInteraction, client::Context};
-use std::time::Duration;
 
 pub async fn seek(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
-    let guild_id = interaction.guild_id.unwrap();
-    let manager = songbird::get(ctx).await.unwrap();
-    let call = manager.get(guild_id).unwrap();
-
     let args = interaction.data.options.clone();
     let seek_time = &args.first().unwrap().value;
 
     let timestamp_str = seek_time.as_str().unwrap();
-    let mut units_iter = timestamp_str.split(':');
+    let target = parse_seek_target(timestamp_str);
+    let target = verify(target, ParrotError::Other(FAIL_INVALID_TIMESTAMP))?;
 
-    let minutes = units_iter.next().and_then(|c| c.parse::<u64>().ok());
-    let minutes = verify(minutes, ParrotError::Other(FAIL_MINUTES_PARSING))?;
-
-    let seconds = units_iter.next().and_then(|c| c.parse::<u64>().ok());
-    let seconds = verify(seconds, ParrotError::Other(FAIL_SECONDS_PARSING))?;
+    seek_current_track(ctx, interaction, target).await
+}
 
-    let timestamp = minutes * 60 + seconds;
+/// Seeks the current track, refusing to go past its end.
+pub async fn seek_current_track(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+    target: SeekTarget,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
 
     let handler = call.lock().await;
     let track = handler
@@ -33,13 +37,33 @@ pub async fn seek(ctx: &Context, interaction: &mut CommandInteraction) -> Result
         .ok_or(ParrotError::NothingPlaying)?;
     drop(handler);
 
-    let _ = track.seek(Duration::from_secs(timestamp));
+    let position = track.get_info().await.unwrap().position;
+
+    // timestamps refer to the track itself, not the sped up or slowed down playback
+    let typemap = track.typemap().read().await;
+    let tempo = get_playback_tempo(&typemap);
+    let duration = typemap
+        .get::<AuxMetadataTypeMapKey>()
+        .and_then(|metadata| metadata.duration);
+    drop(typemap);
+
+    let position = position.mul_f64(tempo);
+    let timestamp = match target {
+        SeekTarget::Absolute(timestamp) => timestamp,
+        SeekTarget::Forward(offset) => position + offset,
+        SeekTarget::Backward(offset) => position.saturating_sub(offset),
+    };
+
+    let in_bounds = duration.map_or(true, |duration| timestamp < duration);
+    verify(in_bounds, ParrotError::Other(FAIL_SEEK_OUT_OF_BOUNDS))?;
+
+    let _ = track.seek(timestamp.div_f64(tempo));
 
     create_response(
         &ctx.http,
         interaction,
         ParrotMessage::Seek {
-            timestamp: timestamp_str.to_owned(),
+            timestamp: get_human_readable_timestamp(Some(timestamp)),
         },
     )
     .await
@@ -0,0 +1,27 @@
+use crate::{
+    commands::play::reload_queue, errors::ParrotError, guild::cache::GuildCacheMap,
+    handlers::track_end::update_queue_messages, messaging::message::ParrotMessage,
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn speed(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let args = interaction.data.options.clone();
+    let speed = args.first().unwrap().value.as_f64().unwrap();
+
+    let mut data = ctx.data.write().await;
+    let cache_map = data.get_mut::<GuildCacheMap>().unwrap();
+    let cache = cache_map.entry(guild_id).or_default();
+    cache.playback_rate.speed = speed;
+    drop(data);
+
+    let queue = reload_queue(&call, &ctx.http, &ctx.data, guild_id).await?;
+
+    create_response(&ctx.http, interaction, ParrotMessage::Speed { speed }).await?;
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::{
+    commands::play::reload_queue,
+    errors::ParrotError,
+    guild::settings::{GuildSettings, GuildSettingsMap},
+    messaging::message::ParrotMessage,
+    utils::create_response,
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn sponsorblock(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+    guild_settings.toggle_sponsorblock();
+    guild_settings.save()?;
+
+    let sponsorblock = guild_settings.sponsorblock;
+    drop(data);
+
+    reload_queue(&call, &ctx.http, &ctx.data, guild_id).await?;
+
+    if sponsorblock {
+        create_response(&ctx.http, interaction, ParrotMessage::SponsorBlockOn).await
+    } else {
+        create_response(&ctx.http, interaction, ParrotMessage::SponsorBlockOff).await
+    }
+}
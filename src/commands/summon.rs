@@ -1,17 +1,19 @@
 use crate::{
     connection::get_voice_channel_for_user,
     errors::ParrotError,
-    handlers::{IdleHandler, TrackEndHandler},
+    handlers::{IdleHandler, PersistQueueHandler, TrackEndHandler},
     messaging::message::ParrotMessage,
     utils::create_response,
 };
 use serenity::{
-    all::{ChannelId, CommandInteraction},
+    all::{ChannelId, CommandInteraction, GuildId},
     client::Context,
-    prelude::Mentionable,
+    prelude::{Mentionable, Mutex},
 };
-use songbird::{Event, TrackEvent};
-use std::time::Duration;
+use songbird::{Call, Event, TrackEvent};
+use std::{sync::Arc, time::Duration};
+
+const PERSIST_QUEUE_INTERVAL: u64 = 5;
 
 pub async fn summon(
     ctx: &Context,
@@ -41,30 +43,7 @@ pub async fn summon(
 
     // register events
     if let Some(call) = manager.get(guild.id) {
-        let mut handler = call.lock().await;
-
-        handler.remove_all_global_events();
-
-        handler.add_global_event(
-            Event::Periodic(Duration::from_secs(1), None),
-            IdleHandler {
-                http: ctx.http.clone(),
-                manager,
-                interaction: interaction.clone(),
-                limit: 60 * 10,
-                count: Default::default(),
-            },
-        );
-
-        handler.add_global_event(
-            Event::Track(TrackEvent::End),
-            TrackEndHandler {
-                http: ctx.http.clone(),
-                guild_id: guild.id,
-                call: call.clone(),
-                ctx_data: ctx.data.clone(),
-            },
-        );
+        register_events(ctx, &call, guild.id, interaction.channel_id).await;
     }
 
     if send_reply {
@@ -80,3 +59,46 @@ pub async fn summon(
 
     Ok(())
 }
+
+pub async fn register_events(
+    ctx: &Context,
+    call: &Arc<Mutex<Call>>,
+    guild_id: GuildId,
+    text_channel_id: ChannelId,
+) {
+    let manager = songbird::get(ctx).await.unwrap();
+    let mut handler = call.lock().await;
+
+    handler.remove_all_global_events();
+
+    handler.add_global_event(
+        Event::Periodic(Duration::from_secs(1), None),
+        IdleHandler {
+            http: ctx.http.clone(),
+            manager,
+            guild_id,
+            channel_id: text_channel_id,
+            limit: 60 * 10,
+            count: Default::default(),
+        },
+    );
+
+    handler.add_global_event(
+        Event::Track(TrackEvent::End),
+        TrackEndHandler {
+            http: ctx.http.clone(),
+            guild_id,
+            call: call.clone(),
+            ctx_data: ctx.data.clone(),
+        },
+    );
+
+    handler.add_global_event(
+        Event::Periodic(Duration::from_secs(PERSIST_QUEUE_INTERVAL), None),
+        PersistQueueHandler {
+            guild_id,
+            text_channel_id,
+            call: call.clone(),
+        },
+    );
+}
@@ -0,0 +1,79 @@
+use crate::{
+    errors::{verify, ParrotError},
+    guild::stored_queue::GuildStoredQueueMap,
+    handlers::track_end::update_queue_messages,
+    messaging::{message::ParrotMessage, messages::FAIL_SWAP_NOT_STORED},
+    utils::{create_response, AuxMetadataTypeMapKey},
+};
+use serenity::{all::CommandInteraction, client::Context};
+use songbird::tracks::TrackHandle;
+
+pub async fn swap(ctx: &Context, interaction: &mut CommandInteraction) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let args = interaction.data.options.clone();
+    let a = args.first().unwrap().value.as_i64().unwrap() as isize;
+    let b = args.get(1).unwrap().value.as_i64().unwrap() as isize;
+
+    let handler = call.lock().await;
+    let queue = handler.queue().current_queue();
+    let queue_len = queue.len() as isize;
+
+    verify(queue_len > 1, ParrotError::QueueEmpty)?;
+    verify(
+        a < queue_len,
+        ParrotError::NotInRange("a", a, 1, queue_len - 1),
+    )?;
+    verify(
+        b < queue_len,
+        ParrotError::NotInRange("b", b, 1, queue_len - 1),
+    )?;
+
+    let (a, b) = (a as usize, b as usize);
+    let first = get_title(&queue[a]).await;
+    let second = get_title(&queue[b]).await;
+
+    let mut data = ctx.data.write().await;
+    let guild_stored_queue = data
+        .get_mut::<GuildStoredQueueMap>()
+        .unwrap()
+        .get_mut(&guild_id)
+        .unwrap();
+
+    // a stored track can't trade places with one that doesn't come back when the queue loops
+    let stored_len = guild_stored_queue.queue.len();
+    verify(
+        (a < stored_len) == (b < stored_len),
+        ParrotError::Other(FAIL_SWAP_NOT_STORED),
+    )?;
+
+    handler.queue().modify_queue(|v| {
+        v.swap(a, b);
+
+        if a < stored_len {
+            guild_stored_queue.queue.swap(a, b);
+        }
+    });
+
+    // refetch the queue after modification
+    let queue = handler.queue().current_queue();
+    drop(handler);
+    drop(data);
+
+    create_response(
+        &ctx.http,
+        interaction,
+        ParrotMessage::Swap { first, second },
+    )
+    .await?;
+    update_queue_messages(&ctx.http, &ctx.data, &queue, guild_id).await;
+    Ok(())
+}
+
+async fn get_title(track: &TrackHandle) -> String {
+    let typemap = track.typemap().read().await;
+    let metadata = typemap.get::<AuxMetadataTypeMapKey>().unwrap();
+    metadata.title.clone().unwrap_or_default()
+}
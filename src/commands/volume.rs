@@ -0,0 +1,52 @@
+use crate::{
+    errors::ParrotError,
+    guild::settings::{GuildSettings, GuildSettingsMap},
+    messaging::message::ParrotMessage,
+    utils::{create_response, volume_to_gain},
+};
+use serenity::{all::CommandInteraction, client::Context};
+
+pub async fn volume(
+    ctx: &Context,
+    interaction: &mut CommandInteraction,
+) -> Result<(), ParrotError> {
+    let guild_id = interaction.guild_id.unwrap();
+    let manager = songbird::get(ctx).await.unwrap();
+    let call = manager.get(guild_id).unwrap();
+
+    let args = interaction.data.options.clone();
+    let level = args.first().map(|arg| arg.value.as_i64().unwrap() as u8);
+
+    let mut data = ctx.data.write().await;
+    let settings = data.get_mut::<GuildSettingsMap>().unwrap();
+
+    let guild_settings = settings
+        .entry(guild_id)
+        .or_insert_with(|| GuildSettings::new(guild_id));
+
+    let Some(level) = level else {
+        let volume = guild_settings.default_volume;
+        drop(data);
+
+        return create_response(
+            &ctx.http,
+            interaction,
+            ParrotMessage::VolumeCurrent { volume },
+        )
+        .await;
+    };
+
+    guild_settings.set_default_volume(level);
+    guild_settings.save()?;
+    let volume = guild_settings.default_volume;
+    drop(data);
+
+    // apply the new level to the playing track as well as everything already queued
+    let handler = call.lock().await;
+    for track in handler.queue().current_queue() {
+        track.set_volume(volume_to_gain(volume)).ok();
+    }
+    drop(handler);
+
+    create_response(&ctx.http, interaction, ParrotMessage::Volume { volume }).await
+}
@@ -1,5 +1,6 @@
+use crate::guild::settings::QueueLimit;
 use crate::messaging::messages::{
-    FAIL_ANOTHER_CHANNEL, FAIL_AUTHOR_DISCONNECTED, FAIL_AUTHOR_NOT_FOUND,
+    FAIL_ANOTHER_CHANNEL, FAIL_AUTHOR_DISCONNECTED, FAIL_AUTHOR_NOT_FOUND, FAIL_DUPLICATE_TRACK,
     FAIL_NO_VOICE_CONNECTION, FAIL_WRONG_CHANNEL, NOTHING_IS_PLAYING, QUEUE_IS_EMPTY,
     TRACK_INAPPROPRIATE, TRACK_NOT_FOUND,
 };
@@ -20,6 +21,8 @@ pub enum ParrotError {
     WrongVoiceChannel,
     AuthorNotFound,
     NothingPlaying,
+    LimitReached(QueueLimit),
+    DuplicateTrack,
     TrackFail(PlayError),
     AlreadyConnected(Mention),
     Serenity(SerenityError),
@@ -53,6 +56,8 @@ impl Display for ParrotError {
                 f.write_fmt(format_args!("{} {}", FAIL_ANOTHER_CHANNEL, mention))
             }
             Self::NothingPlaying => f.write_str(NOTHING_IS_PLAYING),
+            Self::LimitReached(limit) => f.write_str(&format!("{limit}")),
+            Self::DuplicateTrack => f.write_str(FAIL_DUPLICATE_TRACK),
             Self::TrackFail(err) => match err {
                 PlayError::Parse(error) => {
                     if error.to_string().contains("Sign in to confirm your age") {
@@ -87,6 +92,7 @@ impl PartialEq for ParrotError {
             (Self::AlreadyConnected(l0), Self::AlreadyConnected(r0)) => {
                 l0.to_string() == r0.to_string()
             }
+            (Self::LimitReached(l0), Self::LimitReached(r0)) => l0 == r0,
             (Self::Serenity(l0), Self::Serenity(r0)) => format!("{l0:?}") == format!("{r0:?}"),
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, str::FromStr};
+
+pub type AudioFilters = BTreeSet<AudioFilter>;
+
+pub const MIN_PLAYBACK_RATE: f64 = 0.5;
+pub const MAX_PLAYBACK_RATE: f64 = 2.0;
+
+// ffmpeg's sample rate, which pitch shifting temporarily changes
+const FILTER_SAMPLE_RATE: f64 = 48000.0;
+
+// atempo only accepts factors within this range, so larger changes are chained
+const MIN_ATEMPO: f64 = 0.5;
+const MAX_ATEMPO: f64 = 2.0;
+
+// single-pass EBU R128 normalization, targeting the loudness most streaming services use
+const LOUDNORM_FILTER: &str = "loudnorm=I=-14:TP=-1.5:LRA=11";
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AudioFilter {
+    BassBoost,
+    Nightcore,
+    Vaporwave,
+    EightD,
+    Karaoke,
+}
+
+impl AudioFilter {
+    pub const ALL: [AudioFilter; 5] = [
+        AudioFilter::BassBoost,
+        AudioFilter::Nightcore,
+        AudioFilter::Vaporwave,
+        AudioFilter::EightD,
+        AudioFilter::Karaoke,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BassBoost => "bassboost",
+            Self::Nightcore => "nightcore",
+            Self::Vaporwave => "vaporwave",
+            Self::EightD => "8d",
+            Self::Karaoke => "karaoke",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::BassBoost => "Bass boost",
+            Self::Nightcore => "Nightcore",
+            Self::Vaporwave => "Vaporwave",
+            Self::EightD => "8D",
+            Self::Karaoke => "Karaoke",
+        }
+    }
+
+    /// The ffmpeg `-af` graph implementing this filter.
+    /// Rate changing filters resample first so the input sample rate doesn't skew the result.
+    pub fn ffmpeg_filter(&self) -> &'static str {
+        match self {
+            Self::BassBoost => "bass=g=10:f=110:w=0.6",
+            Self::Nightcore => "aresample=48000,asetrate=60000,aresample=48000",
+            Self::Vaporwave => "aresample=48000,asetrate=38400,aresample=48000",
+            Self::EightD => "apulsator=hz=0.125",
+            Self::Karaoke => "pan=stereo|c0=c0-c1|c1=c1-c0",
+        }
+    }
+
+    /// How much faster than the source this filter plays back.
+    pub fn tempo(&self) -> f64 {
+        match self {
+            Self::Nightcore => 1.25,
+            Self::Vaporwave => 0.8,
+            _ => 1.0,
+        }
+    }
+}
+
+impl FromStr for AudioFilter {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AudioFilter::ALL
+            .iter()
+            .copied()
+            .find(|filter| filter.name() == s)
+            .ok_or(())
+    }
+}
+
+/// Speed and pitch multipliers applied on top of the filter presets, independently of each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlaybackRate {
+    pub speed: f64,
+    pub pitch: f64,
+}
+
+impl Default for PlaybackRate {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            pitch: 1.0,
+        }
+    }
+}
+
+impl PlaybackRate {
+    /// The ffmpeg filters implementing this rate, if it changes anything.
+    /// Pitch is shifted by resampling, which also changes the tempo, so atempo makes up the difference.
+    pub fn ffmpeg_filters(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+
+        if self.pitch != 1.0 {
+            filters.push(format!(
+                "aresample={rate},asetrate={shifted},aresample={rate}",
+                rate = FILTER_SAMPLE_RATE,
+                shifted = (FILTER_SAMPLE_RATE * self.pitch).round(),
+            ));
+        }
+
+        let mut tempo = self.speed / self.pitch;
+
+        while tempo < MIN_ATEMPO {
+            filters.push(format!("atempo={}", MIN_ATEMPO));
+            tempo /= MIN_ATEMPO;
+        }
+
+        while tempo > MAX_ATEMPO {
+            filters.push(format!("atempo={}", MAX_ATEMPO));
+            tempo /= MAX_ATEMPO;
+        }
+
+        if tempo != 1.0 {
+            filters.push(format!("atempo={}", tempo));
+        }
+
+        filters
+    }
+}
+
+/// Chains the given filters into a single ffmpeg filter graph, if there are any.
+/// Normalization goes last so it measures the loudness of the already filtered audio.
+pub fn build_filter_graph(
+    filters: &AudioFilters,
+    rate: &PlaybackRate,
+    normalize: bool,
+) -> Option<String> {
+    let mut graph = filters
+        .iter()
+        .map(|filter| filter.ffmpeg_filter().to_string())
+        .collect::<Vec<_>>();
+
+    graph.extend(rate.ffmpeg_filters());
+
+    if normalize {
+        graph.push(LOUDNORM_FILTER.to_string());
+    }
+
+    if graph.is_empty() {
+        return None;
+    }
+
+    Some(graph.join(","))
+}
+
+/// The combined playback rate of the given filters and speed relative to the source.
+pub fn calculate_tempo(filters: &AudioFilters, rate: &PlaybackRate) -> f64 {
+    filters.iter().map(|filter| filter.tempo()).product::<f64>() * rate.speed
+}
+
+pub fn format_playback_rate(rate: f64) -> String {
+    format!("{}x", rate)
+}
+
+pub fn format_filters(filters: &AudioFilters) -> String {
+    filters
+        .iter()
+        .map(|filter| filter.label())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
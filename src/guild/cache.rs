@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
 };
 
@@ -11,12 +11,29 @@ use serenity::{
     prelude::{RwLock, TypeMapKey},
 };
 
+use crate::{filters::PlaybackRate, guild::persisted_queue::PersistedTrack};
+
+const HISTORY_SIZE: usize = 100;
+
 type QueueMessage = (Message, Arc<RwLock<usize>>);
 
 #[derive(Default)]
 pub struct GuildCache {
     pub queue_messages: Vec<QueueMessage>,
     pub current_skip_votes: HashSet<UserId>,
+    pub playback_rate: PlaybackRate,
+    /// Played tracks, the most recent one last.
+    pub history: VecDeque<PersistedTrack>,
+}
+
+impl GuildCache {
+    pub fn push_history(&mut self, track: PersistedTrack) {
+        if self.history.len() >= HISTORY_SIZE {
+            self.history.pop_front();
+        }
+
+        self.history.push_back(track);
+    }
 }
 
 pub struct GuildCacheMap;
@@ -1,3 +1,5 @@
 pub mod cache;
+pub mod persisted_queue;
+pub mod playlists;
 pub mod settings;
 pub mod stored_queue;
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use serenity::model::id::{ChannelId, GuildId, UserId};
+use songbird::{input::AuxMetadata, tracks::TrackHandle};
+use std::{
+    fs::{create_dir_all, remove_file, OpenOptions},
+    io::{BufReader, BufWriter},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{
+    commands::play::QueryType,
+    errors::ParrotError,
+    guild::settings::SETTINGS_PATH,
+    utils::{AuxMetadataTypeMapKey, PlaybackRange, PlaybackRangeTypeMapKey, RequesterTypeMapKey},
+};
+
+/// Mirrors songbird's [`AuxMetadata`] so it can be (de)serialized with serde.
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "AuxMetadata")]
+struct AuxMetadataDef {
+    track: Option<String>,
+    artist: Option<String>,
+    album: Option<String>,
+    date: Option<String>,
+    channels: Option<u8>,
+    channel: Option<String>,
+    start_time: Option<Duration>,
+    duration: Option<Duration>,
+    sample_rate: Option<u32>,
+    source_url: Option<String>,
+    title: Option<String>,
+    thumbnail: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PersistedTrack {
+    pub source_url: String,
+    #[serde(with = "AuxMetadataDef")]
+    pub metadata: AuxMetadata,
+    pub requester: UserId,
+    #[serde(default)]
+    pub range: PlaybackRange,
+}
+
+impl PersistedTrack {
+    /// Captures what's needed to queue the given track again without refetching its metadata.
+    pub async fn from_track(track: &TrackHandle) -> Option<PersistedTrack> {
+        let typemap = track.typemap().read().await;
+        let metadata = typemap.get::<AuxMetadataTypeMapKey>()?;
+        let requester = typemap.get::<RequesterTypeMapKey>()?;
+        let range = typemap
+            .get::<PlaybackRangeTypeMapKey>()
+            .copied()
+            .unwrap_or_default();
+
+        Some(PersistedTrack {
+            source_url: metadata.source_url.clone()?,
+            metadata: metadata.clone(),
+            requester: *requester,
+            range,
+        })
+    }
+}
+
+/// A snapshot of a guild's songbird queue, written next to its settings so
+/// playback can pick up where it left off after a restart.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PersistedQueue {
+    pub guild_id: GuildId,
+    pub voice_channel_id: ChannelId,
+    pub text_channel_id: ChannelId,
+    pub position: Duration,
+    pub tracks: Vec<PersistedTrack>,
+}
+
+impl PersistedQueue {
+    pub async fn from_tracks(
+        guild_id: GuildId,
+        voice_channel_id: ChannelId,
+        text_channel_id: ChannelId,
+        tracks: &[TrackHandle],
+    ) -> PersistedQueue {
+        let position = match tracks.first() {
+            Some(track) => track
+                .get_info()
+                .await
+                .map(|info| info.position)
+                .unwrap_or_default(),
+            None => Duration::ZERO,
+        };
+
+        let mut persisted_tracks = Vec::with_capacity(tracks.len());
+
+        for track in tracks {
+            if let Some(persisted_track) = PersistedTrack::from_track(track).await {
+                persisted_tracks.push(persisted_track);
+            }
+        }
+
+        // the current track resumes from the saved position instead
+        if let Some(track) = persisted_tracks.first_mut() {
+            track.range.start = None;
+        }
+
+        PersistedQueue {
+            guild_id,
+            voice_channel_id,
+            text_channel_id,
+            position,
+            tracks: persisted_tracks,
+        }
+    }
+
+    /// The queries that queue the saved tracks again, for the guild's stored queue.
+    pub fn stored_queue(&self) -> Vec<(QueryType, UserId)> {
+        self.tracks
+            .iter()
+            .map(|track| {
+                (
+                    QueryType::from_source_url(track.source_url.clone()),
+                    track.requester,
+                )
+            })
+            .collect()
+    }
+
+    pub fn load(guild_id: GuildId) -> Result<Option<PersistedQueue>, ParrotError> {
+        let path = Self::path(guild_id);
+        if !Path::new(&path).exists() {
+            return Ok(None);
+        }
+
+        let file = OpenOptions::new().read(true).open(path)?;
+        let reader = BufReader::new(file);
+        Ok(Some(serde_json::from_reader(reader)?))
+    }
+
+    pub fn save(&self) -> Result<(), ParrotError> {
+        create_dir_all(SETTINGS_PATH.as_str())?;
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(Self::path(self.guild_id))?;
+
+        let writer = BufWriter::new(file);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    pub fn delete(guild_id: GuildId) -> Result<(), ParrotError> {
+        let path = Self::path(guild_id);
+        if !Path::new(&path).exists() {
+            return Ok(());
+        }
+
+        remove_file(path)?;
+        Ok(())
+    }
+
+    fn path(guild_id: GuildId) -> String {
+        format!("{}/{}.queue.json", SETTINGS_PATH.as_str(), guild_id)
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use serenity::model::id::{GuildId, UserId};
+use std::{
+    collections::BTreeMap,
+    fs::{create_dir_all, OpenOptions},
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use crate::{
+    errors::ParrotError,
+    guild::{persisted_queue::PersistedTrack, settings::SETTINGS_PATH},
+};
+
+/// Who a set of saved playlists belongs to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaylistScope {
+    User(UserId),
+    Guild(GuildId),
+}
+
+/// Named snapshots of queues, owned by a single user or shared by a whole guild.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SavedPlaylists {
+    pub playlists: BTreeMap<String, Vec<PersistedTrack>>,
+}
+
+impl SavedPlaylists {
+    pub fn load(scope: PlaylistScope) -> Result<SavedPlaylists, ParrotError> {
+        let path = Self::path(scope);
+        if !Path::new(&path).exists() {
+            return Ok(SavedPlaylists::default());
+        }
+
+        let file = OpenOptions::new().read(true).open(path)?;
+        let reader = BufReader::new(file);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn save(&self, scope: PlaylistScope) -> Result<(), ParrotError> {
+        create_dir_all(Self::dir())?;
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(Self::path(scope))?;
+
+        let writer = BufWriter::new(file);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    fn dir() -> String {
+        format!("{}/playlists", SETTINGS_PATH.as_str())
+    }
+
+    fn path(scope: PlaylistScope) -> String {
+        match scope {
+            PlaylistScope::User(user_id) => format!("{}/user-{}.json", Self::dir(), user_id),
+            PlaylistScope::Guild(guild_id) => format!("{}/guild-{}.json", Self::dir(), guild_id),
+        }
+    }
+}
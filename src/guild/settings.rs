@@ -7,15 +7,23 @@ use std::{
     fs::{create_dir_all, OpenOptions},
     io::{BufReader, BufWriter},
     path::Path,
+    time::Duration,
 };
 
-use crate::errors::ParrotError;
+use crate::{
+    errors::ParrotError,
+    filters::{AudioFilter, AudioFilters},
+    utils::{compare_domains, get_youtube_video_id},
+};
 
 const DEFAULT_SETTINGS_PATH: &str = "data/settings";
 const DEFAULT_ALLOWED_DOMAINS: [&str; 2] = ["youtube.com", "youtu.be"];
+pub const DEFAULT_VOLUME: u8 = 100;
+pub const MAX_VOLUME: u8 = 200;
+pub const MAX_CROSSFADE: u8 = 12;
 
 lazy_static! {
-    static ref SETTINGS_PATH: String =
+    pub static ref SETTINGS_PATH: String =
         env::var("SETTINGS_PATH").unwrap_or(DEFAULT_SETTINGS_PATH.to_string());
 }
 
@@ -26,6 +34,78 @@ pub struct GuildSettings {
     pub allowed_domains: HashSet<String>,
     pub banned_domains: HashSet<String>,
     pub queue_loop: bool,
+    #[serde(default = "default_volume")]
+    pub default_volume: u8,
+    #[serde(default)]
+    pub filters: AudioFilters,
+    #[serde(default)]
+    pub normalize: bool,
+    #[serde(default)]
+    pub crossfade: u8,
+    #[serde(default)]
+    pub autoplay: bool,
+    #[serde(default)]
+    pub sponsorblock: bool,
+    #[serde(default)]
+    pub fair_queue: bool,
+    #[serde(default)]
+    pub max_queue_length: Option<usize>,
+    #[serde(default)]
+    pub max_tracks_per_user: Option<usize>,
+    #[serde(default)]
+    pub max_playlist_size: Option<usize>,
+    /// In seconds.
+    #[serde(default)]
+    pub max_track_duration: Option<u64>,
+    #[serde(default)]
+    pub duplicates: DuplicatePolicy,
+}
+
+/// Whether a track that's already queued can be queued again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum DuplicatePolicy {
+    #[default]
+    Allow,
+    Reject,
+    /// Only rejects tracks found among the last given number of queued tracks.
+    RejectWithin(usize),
+}
+
+impl DuplicatePolicy {
+    /// Whether queueing `url` goes against this policy, given the URLs already
+    /// queued from front to back.
+    pub fn rejects(&self, queued_urls: &[String], url: &str) -> bool {
+        let recent = match self {
+            Self::Allow => return false,
+            Self::Reject => queued_urls,
+            Self::RejectWithin(n) => &queued_urls[queued_urls.len().saturating_sub(*n)..],
+        };
+
+        recent
+            .iter()
+            .any(|queued_url| is_same_track(queued_url, url))
+    }
+}
+
+/// YouTube links to the same video come in many shapes, so those are compared by video ID.
+pub fn is_same_track(a: &str, b: &str) -> bool {
+    match (get_youtube_video_id(a), get_youtube_video_id(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// A limit on what can be queued in a guild, along with the value it's set to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueueLimit {
+    QueueLength(usize),
+    TracksPerUser(usize),
+    PlaylistSize(usize),
+    TrackDuration(Duration),
+}
+
+fn default_volume() -> u8 {
+    DEFAULT_VOLUME
 }
 
 impl GuildSettings {
@@ -41,6 +121,18 @@ impl GuildSettings {
             queue_loop: false,
             allowed_domains,
             banned_domains: HashSet::new(),
+            default_volume: DEFAULT_VOLUME,
+            filters: AudioFilters::new(),
+            normalize: false,
+            crossfade: 0,
+            autoplay: false,
+            sponsorblock: false,
+            fair_queue: false,
+            max_queue_length: None,
+            max_tracks_per_user: None,
+            max_playlist_size: None,
+            max_track_duration: None,
+            duplicates: DuplicatePolicy::Allow,
         }
     }
 
@@ -79,10 +171,47 @@ impl GuildSettings {
         self.autopause = !self.autopause;
     }
 
+    pub fn toggle_autoplay(&mut self) {
+        self.autoplay = !self.autoplay;
+    }
+
+    pub fn toggle_fair_queue(&mut self) {
+        self.fair_queue = !self.fair_queue;
+    }
+
     pub fn toggle_queue_loop(&mut self) {
         self.queue_loop = !self.queue_loop;
     }
 
+    pub fn toggle_normalize(&mut self) {
+        self.normalize = !self.normalize;
+    }
+
+    pub fn toggle_sponsorblock(&mut self) {
+        self.sponsorblock = !self.sponsorblock;
+    }
+
+    pub fn set_default_volume(&mut self, volume: u8) {
+        self.default_volume = volume.min(MAX_VOLUME);
+    }
+
+    pub fn set_crossfade(&mut self, seconds: u8) {
+        self.crossfade = seconds.min(MAX_CROSSFADE);
+    }
+
+    /// Toggles a filter on or off, returning whether it's now enabled.
+    pub fn toggle_filter(&mut self, filter: AudioFilter) -> bool {
+        if self.filters.remove(&filter) {
+            return false;
+        }
+
+        self.filters.insert(filter)
+    }
+
+    pub fn clear_filters(&mut self) {
+        self.filters.clear();
+    }
+
     pub fn set_allowed_domains(&mut self, allowed_str: &str) {
         let allowed = allowed_str
             .split(';')
@@ -103,6 +232,21 @@ impl GuildSettings {
         self.banned_domains = banned;
     }
 
+    /// Whether tracks from `domain` can be played, following the allowed or banned list.
+    pub fn is_domain_allowed(&self, domain: &str) -> bool {
+        let is_allowed = self
+            .allowed_domains
+            .iter()
+            .any(|d| compare_domains(d, domain));
+
+        let is_banned = self
+            .banned_domains
+            .iter()
+            .any(|d| compare_domains(d, domain));
+
+        !is_banned && (!self.banned_domains.is_empty() || is_allowed)
+    }
+
     pub fn update_domains(&mut self) {
         if !self.allowed_domains.is_empty() && !self.banned_domains.is_empty() {
             self.banned_domains.clear();
@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
-use serenity::model::id::GuildId;
+use serenity::{
+    model::id::{GuildId, UserId},
+    prelude::{RwLock, TypeMap},
+};
 use songbird::typemap::TypeMapKey;
 
 use crate::commands::play::QueryType;
@@ -8,7 +11,7 @@ use crate::commands::play::QueryType;
 #[derive(Default, Debug, Clone)]
 pub struct GuildStoredQueue {
     pub continue_play: bool,
-    pub queue: Vec<QueryType>,
+    pub queue: Vec<(QueryType, UserId)>,
 }
 
 impl GuildStoredQueue {
@@ -25,3 +28,22 @@ pub struct GuildStoredQueueMap;
 impl TypeMapKey for GuildStoredQueueMap {
     type Value = HashMap<GuildId, GuildStoredQueue>;
 }
+
+/// Records a query that made it into the queue at its back, so it's queued again when
+/// the queue loops.
+pub async fn store_query(
+    data: &Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    query_type: QueryType,
+    requester: UserId,
+) {
+    let mut data = data.write().await;
+    let guild_stored_queue = data
+        .get_mut::<GuildStoredQueueMap>()
+        .unwrap()
+        .entry(guild_id)
+        .or_insert_with(GuildStoredQueue::new);
+
+    guild_stored_queue.queue.push((query_type, requester));
+    guild_stored_queue.continue_play = true;
+}
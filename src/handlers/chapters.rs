@@ -0,0 +1,54 @@
+use serenity::async_trait;
+use songbird::{Event, EventContext, EventHandler};
+
+use crate::{
+    sources::{
+        attachment::AttachmentSource, chapters::fetch_chapters, library::Library,
+        radio::RadioSource,
+    },
+    utils::{AuxMetadataTypeMapKey, ChaptersTypeMapKey},
+};
+
+/// Looks up a track's chapters the first time it starts playing, so queueing
+/// long playlists doesn't spawn an extra yt-dlp process per track.
+pub struct ChaptersHandler;
+
+#[async_trait]
+impl EventHandler for ChaptersHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(&[(_, track)]) = ctx else {
+            return None;
+        };
+
+        let source_url = track
+            .typemap()
+            .read()
+            .await
+            .get::<AuxMetadataTypeMapKey>()?
+            .source_url
+            .clone()?;
+
+        // library files, attachments and radio streams don't have chapters yt-dlp could find
+        if Library::is_library_url(&source_url)
+            || AttachmentSource::is_attachment_url(&source_url)
+            || RadioSource::is_playlist_url(&source_url)
+        {
+            return Some(Event::Cancel);
+        }
+
+        let track = track.clone();
+
+        // the event thread shouldn't wait on yt-dlp
+        tokio::spawn(async move {
+            match fetch_chapters(&source_url).await {
+                Ok(chapters) => {
+                    let mut typemap = track.typemap().write().await;
+                    typemap.insert::<ChaptersTypeMapKey>(chapters);
+                }
+                Err(err) => println!("{}", err),
+            }
+        });
+
+        Some(Event::Cancel)
+    }
+}
@@ -0,0 +1,162 @@
+use serenity::{
+    async_trait,
+    model::id::GuildId,
+    prelude::{RwLock, TypeMap},
+};
+use songbird::{
+    tracks::{LoopState, PlayMode, TrackHandle, TrackQueue},
+    Event, EventContext, EventHandler,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::{guild::settings::GuildSettingsMap, utils::LoopSectionTypeMapKey};
+
+/// How often the volumes are adjusted while two tracks are being crossfaded.
+const FADE_STEP: Duration = Duration::from_millis(100);
+
+/// How long before the crossfade starts the next track begins loading.
+const FADE_PRELOAD_TIME: Duration = Duration::from_secs(5);
+
+/// Periodically checks how much of its track is left and, once it's within the
+/// guild's crossfade duration, starts the next queued track underneath it.
+pub struct CrossfadeHandler {
+    pub ctx_data: Arc<RwLock<TypeMap>>,
+    pub guild_id: GuildId,
+    pub queue: TrackQueue,
+    /// The length of the track as it's played back, i.e. after tempo changes.
+    pub duration: Duration,
+    preloaded: AtomicBool,
+}
+
+impl CrossfadeHandler {
+    pub fn new(
+        ctx_data: Arc<RwLock<TypeMap>>,
+        guild_id: GuildId,
+        queue: TrackQueue,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            ctx_data,
+            guild_id,
+            queue,
+            duration,
+            preloaded: AtomicBool::new(false),
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for CrossfadeHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(&[(state, track)]) = ctx else {
+            return None;
+        };
+
+        let data_rlock = self.ctx_data.read().await;
+        let (crossfade, autopause) = data_rlock
+            .get::<GuildSettingsMap>()?
+            .get(&self.guild_id)
+            .map(|settings| (settings.crossfade, settings.autopause))
+            .unwrap_or_default();
+        drop(data_rlock);
+
+        // without a crossfade the queue's own preloading already makes playback gapless
+        if crossfade == 0 || autopause || state.loops != LoopState::Finite(0) {
+            return None;
+        }
+
+        // only the track at the front of the queue hands over to the next one
+        if self.queue.current()?.uuid() != track.uuid() {
+            return None;
+        }
+
+        // a looped section keeps the track from ever reaching its end
+        if track
+            .typemap()
+            .read()
+            .await
+            .contains_key::<LoopSectionTypeMapKey>()
+        {
+            return None;
+        }
+
+        let crossfade = Duration::from_secs(crossfade.into());
+        let remaining = self.duration.saturating_sub(state.position);
+
+        if remaining > crossfade + FADE_PRELOAD_TIME {
+            return None;
+        }
+
+        let next_track = self.queue.current_queue().get(1)?.clone();
+
+        if !self.preloaded.swap(true, Ordering::Relaxed) {
+            drop(next_track.make_playable());
+        }
+
+        if remaining > crossfade {
+            return None;
+        }
+
+        tokio::spawn(fade(track.clone(), next_track, remaining));
+        Some(Event::Cancel)
+    }
+}
+
+/// Fades `outgoing` out while fading `incoming` in over the given duration,
+/// stopping `outgoing` at the end so the queue moves on.
+async fn fade(outgoing: TrackHandle, incoming: TrackHandle, duration: Duration) {
+    let (Ok(outgoing_info), Ok(incoming_info)) =
+        (outgoing.get_info().await, incoming.get_info().await)
+    else {
+        return;
+    };
+
+    // tracks can play past their reported duration, leaving nothing to fade over
+    if duration.is_zero() {
+        incoming.play().ok();
+        outgoing.stop().ok();
+        return;
+    }
+
+    incoming.set_volume(0.0).ok();
+    if incoming.play().is_err() {
+        incoming.set_volume(incoming_info.volume).ok();
+        return;
+    }
+
+    let steps = (duration.as_millis() / FADE_STEP.as_millis()).max(1) as u32;
+    let mut interval = tokio::time::interval(duration / steps);
+    interval.tick().await;
+
+    for step in 1..=steps {
+        interval.tick().await;
+
+        // the outgoing track was skipped or stopped, let the incoming one play as is
+        let Ok(info) = outgoing.get_info().await else {
+            break;
+        };
+
+        // hold the incoming track back until the outgoing one is resumed and ends normally
+        if info.playing == PlayMode::Pause {
+            incoming.pause().ok();
+            incoming.set_volume(incoming_info.volume).ok();
+            outgoing.set_volume(outgoing_info.volume).ok();
+            return;
+        }
+
+        let progress = step as f32 / steps as f32;
+        outgoing
+            .set_volume(outgoing_info.volume * (1.0 - progress))
+            .ok();
+        incoming.set_volume(incoming_info.volume * progress).ok();
+    }
+
+    incoming.set_volume(incoming_info.volume).ok();
+    outgoing.stop().ok();
+}
@@ -1,16 +1,21 @@
-use serenity::{all::CommandInteraction, async_trait, http::Http};
+use serenity::{
+    async_trait,
+    http::Http,
+    model::id::{ChannelId, GuildId},
+};
 use songbird::{tracks::PlayMode, Event, EventContext, EventHandler, Songbird};
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
     Arc,
 };
 
-use crate::messaging::messages::IDLE_ALERT;
+use crate::{guild::persisted_queue::PersistedQueue, messaging::messages::IDLE_ALERT};
 
 pub struct IdleHandler {
     pub http: Arc<Http>,
     pub manager: Arc<Songbird>,
-    pub interaction: CommandInteraction,
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
     pub limit: usize,
     pub count: Arc<AtomicUsize>,
 }
@@ -35,21 +40,17 @@ impl EventHandler for IdleHandler {
         }
 
         if self.count.fetch_add(1, Ordering::Relaxed) >= self.limit {
-            let guild_id = self.interaction.guild_id?;
-            let call = self.manager.get(guild_id).unwrap();
+            let call = self.manager.get(self.guild_id).unwrap();
 
             let mut handler = call.lock().await;
             handler.remove_all_global_events();
             drop(handler);
 
-            if self.manager.remove(guild_id).await.is_ok() {
+            if self.manager.remove(self.guild_id).await.is_ok() {
                 self.count.store(0, Ordering::Relaxed);
+                PersistedQueue::delete(self.guild_id).ok();
 
-                self.interaction
-                    .channel_id
-                    .say(&self.http, IDLE_ALERT)
-                    .await
-                    .unwrap();
+                self.channel_id.say(&self.http, IDLE_ALERT).await.unwrap();
             }
         }
 
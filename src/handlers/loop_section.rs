@@ -0,0 +1,43 @@
+use serenity::async_trait;
+use songbird::{Event, EventContext, EventHandler};
+
+use crate::utils::{get_playback_tempo, LoopSectionHandlerTypeMapKey, LoopSectionTypeMapKey};
+
+/// Seeks a track back to the start of its looped section whenever playback
+/// reaches the section's end. Only one runs per track, following whichever
+/// section is currently set.
+pub struct LoopSectionHandler;
+
+#[async_trait]
+impl EventHandler for LoopSectionHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(&[(state, track)]) = ctx else {
+            return None;
+        };
+
+        let typemap = track.typemap().read().await;
+
+        // the loop was turned off
+        let Some(section) = typemap.get::<LoopSectionTypeMapKey>().copied() else {
+            drop(typemap);
+            let mut typemap = track.typemap().write().await;
+
+            // it may have been turned back on before the lock was taken
+            if typemap.contains_key::<LoopSectionTypeMapKey>() {
+                return None;
+            }
+
+            typemap.remove::<LoopSectionHandlerTypeMapKey>();
+            return Some(Event::Cancel);
+        };
+
+        let tempo = get_playback_tempo(&typemap);
+        drop(typemap);
+
+        if state.position >= section.end.div_f64(tempo) {
+            let _ = track.seek(section.start.div_f64(tempo));
+        }
+
+        None
+    }
+}
@@ -1,7 +1,21 @@
+pub mod chapters;
+pub mod crossfade;
 pub mod idle;
+pub mod loop_section;
+pub mod persist_queue;
+pub mod playback_range;
 pub mod serenity;
+pub mod skip_segments;
+pub mod stream_title;
 pub mod track_end;
 
+pub use self::chapters::ChaptersHandler;
+pub use self::crossfade::CrossfadeHandler;
 pub use self::idle::IdleHandler;
+pub use self::loop_section::LoopSectionHandler;
+pub use self::persist_queue::PersistQueueHandler;
+pub use self::playback_range::{EndOffsetHandler, StartOffsetHandler};
 pub use self::serenity::SerenityHandler;
+pub use self::skip_segments::SkipSegmentsHandler;
+pub use self::stream_title::StreamTitleHandler;
 pub use self::track_end::TrackEndHandler;
@@ -0,0 +1,49 @@
+use serenity::{
+    async_trait,
+    model::id::{ChannelId, GuildId},
+    prelude::Mutex,
+};
+use songbird::{Call, Event, EventContext, EventHandler};
+use std::sync::Arc;
+
+use crate::guild::persisted_queue::PersistedQueue;
+
+pub struct PersistQueueHandler {
+    pub guild_id: GuildId,
+    pub text_channel_id: ChannelId,
+    pub call: Arc<Mutex<Call>>,
+}
+
+#[async_trait]
+impl EventHandler for PersistQueueHandler {
+    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+        let handler = self.call.lock().await;
+        let tracks = handler.queue().current_queue();
+        let voice_channel_id = handler.current_channel();
+        drop(handler);
+
+        // there's nothing worth restoring, so make sure a stale snapshot isn't picked up later
+        let (Some(voice_channel_id), false) = (voice_channel_id, tracks.is_empty()) else {
+            PersistedQueue::delete(self.guild_id).ok();
+            return None;
+        };
+
+        let voice_channel_id: ChannelId = voice_channel_id.0.into();
+        let persisted_queue = PersistedQueue::from_tracks(
+            self.guild_id,
+            voice_channel_id,
+            self.text_channel_id,
+            &tracks,
+        )
+        .await;
+
+        if let Err(err) = persisted_queue.save() {
+            println!(
+                "[ERROR] Failed to persist guild {} queue due to {}",
+                self.guild_id, err
+            );
+        }
+
+        None
+    }
+}
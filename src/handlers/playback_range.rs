@@ -0,0 +1,43 @@
+use serenity::async_trait;
+use songbird::{Event, EventContext, EventHandler};
+use std::time::Duration;
+
+/// Seeks a track to where its link asked it to start, the first time it plays.
+pub struct StartOffsetHandler {
+    /// The offset in playback time, i.e. after tempo changes.
+    pub start: Duration,
+}
+
+#[async_trait]
+impl EventHandler for StartOffsetHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(&[(_, track)]) = ctx else {
+            return None;
+        };
+
+        let _ = track.seek(self.start);
+        Some(Event::Cancel)
+    }
+}
+
+/// Stops a track once it reaches the point its link asked it to end at.
+pub struct EndOffsetHandler {
+    /// The offset in playback time, i.e. after tempo changes.
+    pub end: Duration,
+}
+
+#[async_trait]
+impl EventHandler for EndOffsetHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(&[(state, track)]) = ctx else {
+            return None;
+        };
+
+        if state.position < self.end {
+            return None;
+        }
+
+        track.stop().ok();
+        Some(Event::Cancel)
+    }
+}
@@ -1,20 +1,32 @@
 use crate::{
     commands::{
-        autopause::*, clear::*, leave::*, manage_sources::*, now_playing::*, pause::*, play::*,
-        queue::*, remove::*, repeat::*, repeat_queue::*, resume::*, seek::*, shuffle::*, skip::*,
-        stop::*, summon::*, version::*, voteskip::*,
+        autopause::*, autoplay::*, chapter::*, clear::*, crossfade::*, dedupe::*, duplicates::*,
+        fair_queue::*, filter::*, forward::*, history::*, jump::*, leave::*, library::*, limits::*,
+        loop_section::*, manage_sources::*, move_track::*, normalize::*, now_playing::*, pause::*,
+        pitch::*, play::*, playlist::*, previous::*, queue::*, remove::*, remove_gone::*,
+        remove_user::*, repeat::*, repeat_queue::*, resume::*, rewind::*, seek::*, shuffle::*,
+        skip::*, speed::*, sponsorblock::*, stop::*, summon::*, swap::*, version::*, volume::*,
+        voteskip::*,
     },
     connection::{check_voice_connections, Connection},
     errors::ParrotError,
-    guild::settings::{GuildSettings, GuildSettingsMap},
+    filters::{AudioFilter, MAX_PLAYBACK_RATE, MIN_PLAYBACK_RATE},
+    guild::{
+        persisted_queue::PersistedQueue,
+        settings::{GuildSettings, GuildSettingsMap, MAX_CROSSFADE, MAX_VOLUME},
+        stored_queue::{GuildStoredQueue, GuildStoredQueueMap},
+    },
     handlers::track_end::update_queue_messages,
-    sources::spotify::{Spotify, SPOTIFY},
+    sources::{
+        library::Library,
+        spotify::{Spotify, SPOTIFY},
+    },
     utils::create_response_text,
 };
 use serenity::{
     all::{
         ActivityData, Command, CommandInteraction, CommandOptionType, CreateCommand,
-        CreateCommandOption, EditMember, Interaction,
+        CreateCommandOption, EditMember, Interaction, Permissions,
     },
     async_trait,
     client::{Context, EventHandler},
@@ -36,11 +48,19 @@ impl EventHandler for SerenityHandler {
         // attempts to authenticate to spotify
         *SPOTIFY.lock().await = Spotify::auth().await;
 
+        // loads the local library's index, scanning it if there's none yet
+        if let Err(err) = Library::load().await {
+            println!("Failed to load the library: {}", err);
+        }
+
         // creates the global application commands
         self.create_commands(&ctx).await;
 
         // loads serialized guild settings
         self.load_guilds_settings(&ctx, &ready).await;
+
+        // rejoins voice channels and resumes queues saved before the last shutdown
+        self.restore_guilds_queues(&ctx, &ready).await;
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
@@ -70,6 +90,8 @@ impl EventHandler for SerenityHandler {
             manager.remove(guild_id).await.ok();
         }
 
+        PersistedQueue::delete(guild_id).ok();
+
         update_queue_messages(&ctx.http, &ctx.data, &[], guild_id).await;
     }
 }
@@ -81,22 +103,204 @@ impl SerenityHandler {
             Vec::from([
                 CreateCommand::new("autopause")
                     .description("Toggles whether to pause after a song ends"),
+                CreateCommand::new("autoplay")
+                    .description("Toggles whether to queue related tracks once the queue runs out"),
+                CreateCommand::new("chapter")
+                    .description("Seeks to a chapter of the current track")
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "target",
+                        "`next`, `previous` or the chapter's name",
+                    )
+                    .required(true)])),
                 CreateCommand::new("clear").description("Clears the queue"),
+                CreateCommand::new("crossfade")
+                    .description("Changes how long tracks fade into each other for this server")
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "seconds",
+                        "Crossfade duration, 0 to disable or leave empty to see the current one",
+                    )
+                    .required(false)
+                    .min_int_value(0)
+                    .max_int_value(MAX_CROSSFADE as u64)])),
+                CreateCommand::new("dedupe")
+                    .description("Removes tracks that are queued more than once"),
+                CreateCommand::new("duplicates")
+                    .description("Shows or changes whether tracks can be queued more than once")
+                    .default_member_permissions(Permissions::MANAGE_GUILD)
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            "policy",
+                            "What to do with tracks that are already queued",
+                        )
+                        .add_string_choice("Allow", "allow")
+                        .add_string_choice("Reject", "reject")
+                        .add_string_choice("Reject if recently queued", "recent"),
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "tracks",
+                            "How many of the last queued tracks count as recent (defaults to 10)",
+                        )
+                        .min_int_value(1),
+                    ])),
+                CreateCommand::new("fairqueue")
+                    .description("Toggles taking turns between everyone's requested tracks"),
+                CreateCommand::new("filter")
+                    .description("Toggles an audio filter for this server")
+                    .set_options(Vec::from([AudioFilter::ALL
+                        .iter()
+                        .fold(
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "preset",
+                                "The filter to toggle",
+                            )
+                            .required(true),
+                            |option, filter| {
+                                option.add_string_choice(filter.label(), filter.name())
+                            },
+                        )
+                        .add_string_choice("Off", FILTER_OFF)])),
+                CreateCommand::new("forward")
+                    .description("Skips ahead in the current track")
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "amount",
+                        "How far to go, e.g. 30s or 1:00 (defaults to 10 seconds)",
+                    )])),
+                CreateCommand::new("history").description("Shows the recently played tracks"),
+                CreateCommand::new("jump")
+                    .description(
+                        "Plays a queued track right away, moving the ones before it to the end",
+                    )
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "index",
+                        "Position of the track in the queue (1 is the next track to be played)",
+                    )
+                    .required(true)
+                    .min_int_value(1)])),
                 CreateCommand::new("leave")
                     .description("Leave the voice channel the bot is connected to"),
+                CreateCommand::new("library")
+                    .description("Manage the local music library")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "rescan",
+                            "Looks for new, changed and removed files in the library",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "stats",
+                            "Shows what's in the library",
+                        ),
+                    ])),
+                CreateCommand::new("limits")
+                    .description("Shows or changes the limits on what can be queued")
+                    .default_member_permissions(Permissions::MANAGE_GUILD)
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "queue",
+                            "Most tracks the queue can hold, 0 for no limit",
+                        )
+                        .min_int_value(0),
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "per_user",
+                            "Most tracks a single person can have queued, 0 for no limit",
+                        )
+                        .min_int_value(0),
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "playlist",
+                            "Most tracks a playlist can add, 0 for no limit",
+                        )
+                        .min_int_value(0),
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "duration",
+                            "Longest track that can be queued in minutes, 0 for no limit",
+                        )
+                        .min_int_value(0),
+                    ])),
+                CreateCommand::new("loop")
+                    .description("Loops a section of the current track")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "section",
+                            "Repeats the part of the track between two timestamps",
+                        )
+                        .set_sub_options(Vec::from([
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "start",
+                                "Where the section starts, e.g. 1:23",
+                            )
+                            .required(true),
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "end",
+                                "Where the section ends, e.g. 1:45",
+                            )
+                            .required(true),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "off",
+                            "Stops looping the section",
+                        ),
+                    ])),
                 CreateCommand::new("managesources")
                     .description("Manage streaming from different sources"),
+                CreateCommand::new("move")
+                    .description("Moves a track to another position in the queue")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "from",
+                            "Position of the track to move",
+                        )
+                        .required(true)
+                        .min_int_value(1),
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "to",
+                            "Position to move the track to",
+                        )
+                        .required(true)
+                        .min_int_value(1),
+                    ])),
+                CreateCommand::new("normalize")
+                    .description("Toggles loudness normalization across tracks"),
                 CreateCommand::new("np")
                     .description("Displays information about the current track"),
                 CreateCommand::new("pause").description("Pauses the current track"),
-                CreateCommand::new("play")
-                    .description("Add a track to the queue")
+                CreateCommand::new("pitch")
+                    .description("Changes the pitch without affecting the speed")
                     .set_options(Vec::from([CreateCommandOption::new(
-                        CommandOptionType::String,
-                        "query",
-                        "The media to play",
+                        CommandOptionType::Number,
+                        "multiplier",
+                        "Pitch multiplier, 1 being the original",
                     )
-                    .required(true)])),
+                    .required(true)
+                    .min_number_value(MIN_PLAYBACK_RATE)
+                    .max_number_value(MAX_PLAYBACK_RATE)])),
+                CreateCommand::new("play")
+                    .description("Add a track to the queue")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            "query",
+                            "The media to play",
+                        ),
+                        play_file_option(),
+                        play_library_option(),
+                    ])),
                 CreateCommand::new("superplay")
                     .description("Add a track to the queue in a special way")
                     .set_options(Vec::from([
@@ -110,8 +314,9 @@ impl SerenityHandler {
                                 CommandOptionType::String,
                                 "query",
                                 "The media to play",
-                            )
-                            .required(true),
+                            ),
+                            play_file_option(),
+                            play_library_option(),
                         ])),
                         CreateCommandOption::new(
                             CommandOptionType::SubCommand,
@@ -123,8 +328,9 @@ impl SerenityHandler {
                                 CommandOptionType::String,
                                 "query",
                                 "The media to play",
-                            )
-                            .required(true),
+                            ),
+                            play_file_option(),
+                            play_library_option(),
                         ])),
                         CreateCommandOption::new(
                             CommandOptionType::SubCommand,
@@ -166,7 +372,96 @@ impl SerenityHandler {
                             .required(true),
                         ])),
                     ])),
-                CreateCommand::new("queue").description("Shows the queue"),
+                CreateCommand::new("playlist")
+                    .description("Saves and loads playlists")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "save",
+                            "Saves the current queue as a playlist",
+                        )
+                        .set_sub_options(Vec::from([
+                            playlist_name_option(),
+                            playlist_scope_option(),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "load",
+                            "Adds a saved playlist to the queue",
+                        )
+                        .set_sub_options(Vec::from([
+                            playlist_name_option(),
+                            playlist_scope_option(),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "list",
+                            "Shows your and this server's saved playlists",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "delete",
+                            "Deletes a saved playlist",
+                        )
+                        .set_sub_options(Vec::from([
+                            playlist_name_option(),
+                            playlist_scope_option(),
+                        ])),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "add",
+                            "Adds a track to a saved playlist",
+                        )
+                        .set_sub_options(Vec::from([
+                            playlist_name_option(),
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "query",
+                                "The track to add, the current one if left empty",
+                            ),
+                            playlist_scope_option(),
+                        ])),
+                    ])),
+                CreateCommand::new("previous")
+                    .description("Goes back to the previously played track"),
+                CreateCommand::new("queue")
+                    .description("Shows, exports or imports the queue")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "show",
+                            "Shows the queue",
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "export",
+                            "Uploads the queue as a file",
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(
+                                CommandOptionType::String,
+                                "format",
+                                "The file's format",
+                            )
+                            .add_string_choice("M3U playlist", "m3u")
+                            .add_string_choice("JSON", "json")
+                            .add_string_choice("Plain list of links", "txt")
+                            .required(true),
+                        ),
+                        CreateCommandOption::new(
+                            CommandOptionType::SubCommand,
+                            "import",
+                            "Adds the tracks listed in an exported queue",
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(
+                                CommandOptionType::Attachment,
+                                "file",
+                                "An M3U, JSON or plain text file",
+                            )
+                            .required(true),
+                        ),
+                    ])),
                 CreateCommand::new("remove")
                     .description("Removes a track from the queue")
                     .set_options(Vec::from([
@@ -185,15 +480,32 @@ impl SerenityHandler {
                         .required(true)
                         .min_int_value(1),
                     ])),
+                CreateCommand::new("removegone")
+                    .description("Removes tracks requested by people who left the voice channel"),
+                CreateCommand::new("removeuser")
+                    .description("Removes every track requested by someone")
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::User,
+                        "user",
+                        "Whose tracks to remove",
+                    )
+                    .required(true)])),
                 CreateCommand::new("repeat").description("Toggles looping for the current track"),
                 CreateCommand::new("repeatqueue").description("Toggles looping for the queue"),
                 CreateCommand::new("resume").description("Resumes the current track"),
+                CreateCommand::new("rewind")
+                    .description("Goes back in the current track")
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "amount",
+                        "How far to go, e.g. 30s or 1:00 (defaults to 10 seconds)",
+                    )])),
                 CreateCommand::new("seek")
                     .description("Seeks current track to the given position")
                     .set_options(Vec::from([CreateCommandOption::new(
                         CommandOptionType::String,
                         "timestamp",
-                        "Timestamp in the format HH:MM:SS",
+                        "Timestamp like 1:23 or 1h2m, or an offset like +30s or -1:00",
                     )
                     .required(true)])),
                 CreateCommand::new("shuffle").description("Shuffles the queue"),
@@ -206,9 +518,49 @@ impl SerenityHandler {
                     )
                     .required(false)
                     .min_int_value(1)])),
+                CreateCommand::new("speed")
+                    .description("Changes the speed without affecting the pitch")
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::Number,
+                        "multiplier",
+                        "Speed multiplier, 1 being the original",
+                    )
+                    .required(true)
+                    .min_number_value(MIN_PLAYBACK_RATE)
+                    .max_number_value(MAX_PLAYBACK_RATE)])),
+                CreateCommand::new("sponsorblock")
+                    .description("Toggles skipping sponsored segments, intros and outros"),
                 CreateCommand::new("stop").description("Stops the bot and clears the queue"),
+                CreateCommand::new("swap")
+                    .description("Swaps the positions of two tracks in the queue")
+                    .set_options(Vec::from([
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "a",
+                            "Position of the first track",
+                        )
+                        .required(true)
+                        .min_int_value(1),
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "b",
+                            "Position of the second track",
+                        )
+                        .required(true)
+                        .min_int_value(1),
+                    ])),
                 CreateCommand::new("summon").description("Summons the bot in your voice channel"),
                 CreateCommand::new("version").description("Displays the current version"),
+                CreateCommand::new("volume")
+                    .description("Changes the playback volume for this server")
+                    .set_options(Vec::from([CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "level",
+                        "Volume percentage, leave empty to see the current one",
+                    )
+                    .required(false)
+                    .min_int_value(0)
+                    .max_int_value(MAX_VOLUME as u64)])),
                 CreateCommand::new("voteskip")
                     .description("Starts a vote to skip the current track"),
             ]),
@@ -237,6 +589,73 @@ impl SerenityHandler {
         }
     }
 
+    async fn restore_guilds_queues(&self, ctx: &Context, ready: &Ready) {
+        println!("[INFO] Restoring guilds' queues");
+        let manager = songbird::get(ctx).await.unwrap();
+
+        for guild in &ready.guilds {
+            // the call survives gateway reconnections, so there's nothing to restore
+            if manager.get(guild.id).is_some() {
+                continue;
+            }
+
+            let persisted_queue = match PersistedQueue::load(guild.id) {
+                Ok(Some(persisted_queue)) if !persisted_queue.tracks.is_empty() => persisted_queue,
+                Ok(_) => continue,
+                Err(err) => {
+                    println!(
+                        "[ERROR] Failed to load guild {} queue due to {}",
+                        guild.id, err
+                    );
+                    continue;
+                }
+            };
+
+            if let Err(err) = self.restore_queue(ctx, &persisted_queue).await {
+                println!(
+                    "[ERROR] Failed to restore guild {} queue due to {}",
+                    guild.id, err
+                );
+            }
+        }
+    }
+
+    async fn restore_queue(
+        &self,
+        ctx: &Context,
+        persisted_queue: &PersistedQueue,
+    ) -> Result<(), ParrotError> {
+        let guild_id = persisted_queue.guild_id;
+        let manager = songbird::get(ctx).await.unwrap();
+
+        let call = manager
+            .join(guild_id, persisted_queue.voice_channel_id)
+            .await
+            .map_err(|_| ParrotError::Other("failed to rejoin the voice channel"))?;
+
+        register_events(ctx, &call, guild_id, persisted_queue.text_channel_id).await;
+
+        let mut queue = Vec::new();
+        for track in &persisted_queue.tracks {
+            queue = enqueue_persisted_track(&call, &ctx.http, &ctx.data, guild_id, track).await?;
+        }
+
+        if let Some(track) = queue.first() {
+            let _ = track.seek(persisted_queue.position);
+        }
+
+        let mut data = ctx.data.write().await;
+        let stored_queue_map = data.get_mut::<GuildStoredQueueMap>().unwrap();
+        let guild_stored_queue = stored_queue_map
+            .entry(guild_id)
+            .or_insert_with(GuildStoredQueue::new);
+
+        guild_stored_queue.queue = persisted_queue.stored_queue();
+        guild_stored_queue.continue_play = true;
+
+        Ok(())
+    }
+
     async fn run_command(
         &self,
         ctx: &Context,
@@ -262,19 +681,32 @@ impl SerenityHandler {
         let user_id = command.user.id;
         let bot_id = ctx.cache.current_user().id;
 
+        // these subcommands can bring the bot in to play, just like /play
+        let subcommand_name = command
+            .data
+            .options
+            .first()
+            .map(|option| option.name.as_str());
+        let queues_tracks = matches!(
+            (command_name, subcommand_name),
+            ("playlist", Some("load")) | ("queue", Some("import"))
+        );
+
         match command_name {
-            "autopause" | "clear" | "leave" | "pause" | "remove" | "repeat" | "repeatqueue"
-            | "resume" | "seek" | "shuffle" | "skip" | "stop" | "voteskip" => {
-                match check_voice_connections(&guild, &user_id, &bot_id) {
-                    Connection::User(_) | Connection::Neither => Err(ParrotError::NotConnected),
-                    Connection::Bot(bot_channel_id) => {
-                        Err(ParrotError::AuthorDisconnected(bot_channel_id.mention()))
-                    }
-                    Connection::Separate(_, _) => Err(ParrotError::WrongVoiceChannel),
-                    _ => Ok(()),
+            "autopause" | "autoplay" | "chapter" | "clear" | "crossfade" | "dedupe"
+            | "fairqueue" | "filter" | "forward" | "jump" | "leave" | "loop" | "move"
+            | "normalize" | "pause" | "pitch" | "previous" | "remove" | "removegone"
+            | "removeuser" | "repeat" | "repeatqueue" | "resume" | "rewind" | "seek"
+            | "shuffle" | "skip" | "speed" | "sponsorblock" | "stop" | "swap" | "volume"
+            | "voteskip" => match check_voice_connections(&guild, &user_id, &bot_id) {
+                Connection::User(_) | Connection::Neither => Err(ParrotError::NotConnected),
+                Connection::Bot(bot_channel_id) => {
+                    Err(ParrotError::AuthorDisconnected(bot_channel_id.mention()))
                 }
-            }
-            "play" | "superplay" | "summon" => {
+                Connection::Separate(_, _) => Err(ParrotError::WrongVoiceChannel),
+                _ => Ok(()),
+            },
+            name if matches!(name, "play" | "superplay" | "summon") || queues_tracks => {
                 match check_voice_connections(&guild, &user_id, &bot_id) {
                     Connection::User(_) => Ok(()),
                     Connection::Bot(_) if command_name == "summon" => {
@@ -299,23 +731,48 @@ impl SerenityHandler {
 
         match command_name {
             "autopause" => autopause(ctx, command).await,
+            "autoplay" => autoplay(ctx, command).await,
+            "chapter" => chapter(ctx, command).await,
             "clear" => clear(ctx, command).await,
+            "crossfade" => crossfade(ctx, command).await,
+            "dedupe" => dedupe(ctx, command).await,
+            "duplicates" => duplicates(ctx, command).await,
+            "fairqueue" => fair_queue(ctx, command).await,
+            "filter" => filter(ctx, command).await,
+            "forward" => forward(ctx, command).await,
+            "history" => history(ctx, command).await,
+            "jump" => jump(ctx, command).await,
             "leave" => leave(ctx, command).await,
+            "library" => library(ctx, command).await,
+            "limits" => limits(ctx, command).await,
+            "loop" => loop_section(ctx, command).await,
             "managesources" => allow(ctx, command).await,
+            "move" => move_track(ctx, command).await,
+            "normalize" => normalize(ctx, command).await,
             "np" => now_playing(ctx, command).await,
             "pause" => pause(ctx, command).await,
+            "pitch" => pitch(ctx, command).await,
             "play" | "superplay" => play(ctx, command).await,
+            "playlist" => playlist(ctx, command).await,
+            "previous" => previous(ctx, command).await,
             "queue" => queue(ctx, command).await,
             "remove" => remove(ctx, command).await,
+            "removegone" => remove_gone(ctx, command).await,
+            "removeuser" => remove_user(ctx, command).await,
             "repeat" => repeat(ctx, command).await,
             "repeatqueue" => repeat_queue(ctx, command).await,
             "resume" => resume(ctx, command).await,
+            "rewind" => rewind(ctx, command).await,
             "seek" => seek(ctx, command).await,
             "shuffle" => shuffle(ctx, command).await,
             "skip" => skip(ctx, command).await,
+            "speed" => speed(ctx, command).await,
+            "sponsorblock" => sponsorblock(ctx, command).await,
             "stop" => stop(ctx, command).await,
             "summon" => summon(ctx, command, true).await,
+            "swap" => swap(ctx, command).await,
             "version" => version(ctx, command).await,
+            "volume" => volume(ctx, command).await,
             "voteskip" => voteskip(ctx, command).await,
             _ => unreachable!(),
         }
@@ -347,3 +804,35 @@ impl SerenityHandler {
             .expect("failed to create response");
     }
 }
+
+fn play_file_option() -> CreateCommandOption {
+    CreateCommandOption::new(
+        CommandOptionType::Attachment,
+        "file",
+        "An audio or video file to play instead",
+    )
+}
+
+fn play_library_option() -> CreateCommandOption {
+    CreateCommandOption::new(
+        CommandOptionType::String,
+        "library",
+        "Search the local music library instead",
+    )
+}
+
+fn playlist_name_option() -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::String, "name", "The playlist's name")
+        .max_length(100)
+        .required(true)
+}
+
+fn playlist_scope_option() -> CreateCommandOption {
+    CreateCommandOption::new(
+        CommandOptionType::String,
+        "scope",
+        "Whose playlists to use, yours by default",
+    )
+    .add_string_choice("Mine", "me")
+    .add_string_choice("This server's", "server")
+}
@@ -0,0 +1,35 @@
+use serenity::async_trait;
+use songbird::{Event, EventContext, EventHandler};
+use std::time::Duration;
+
+use crate::sources::sponsorblock::SkipSegment;
+
+// segments about to end anyway aren't worth a seek
+const MIN_SKIP: Duration = Duration::from_secs(1);
+
+/// Seeks past the track's skip segments as soon as playback enters one of them.
+pub struct SkipSegmentsHandler {
+    pub segments: Vec<SkipSegment>,
+    /// How much faster than its source the track plays back.
+    pub tempo: f64,
+}
+
+#[async_trait]
+impl EventHandler for SkipSegmentsHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(&[(state, track)]) = ctx else {
+            return None;
+        };
+
+        // segments are timed against the source, not the sped up or slowed down playback
+        let position = state.position.mul_f64(self.tempo);
+
+        let segment = self
+            .segments
+            .iter()
+            .find(|segment| segment.start <= position && position + MIN_SKIP < segment.end)?;
+
+        let _ = track.seek(segment.end.div_f64(self.tempo));
+        None
+    }
+}
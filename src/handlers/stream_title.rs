@@ -0,0 +1,43 @@
+use serenity::{
+    async_trait,
+    http::Http,
+    model::id::GuildId,
+    prelude::{RwLock, TypeMap},
+};
+use songbird::{tracks::TrackQueue, Event, EventContext, EventHandler};
+use std::sync::{Arc, Mutex};
+
+use crate::{handlers::track_end::update_queue_messages, utils::AuxMetadataTypeMapKey};
+
+/// Renames a radio track after the song its station says is playing.
+pub struct StreamTitleHandler {
+    pub http: Arc<Http>,
+    pub ctx_data: Arc<RwLock<TypeMap>>,
+    pub guild_id: GuildId,
+    pub queue: TrackQueue,
+    /// The latest title read from the stream, taken once it's been applied.
+    pub stream_title: Arc<Mutex<Option<String>>>,
+}
+
+#[async_trait]
+impl EventHandler for StreamTitleHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(&[(_, track)]) = ctx else {
+            return None;
+        };
+
+        let title = self.stream_title.lock().unwrap().take()?;
+
+        let mut typemap = track.typemap().write().await;
+        if let Some(metadata) = typemap.get_mut::<AuxMetadataTypeMapKey>() {
+            metadata.title = Some(title);
+        }
+        drop(typemap);
+
+        // open queue embeds show the title too
+        let queue = self.queue.current_queue();
+        update_queue_messages(&self.http, &self.ctx_data, &queue, self.guild_id).await;
+
+        None
+    }
+}
@@ -10,13 +10,18 @@ use std::sync::Arc;
 
 use crate::{
     commands::{
+        autoplay::enqueue_related_track,
         play::{normal_query_type_resolver, Mode},
         queue::{
             build_single_nav_btn, calculate_num_pages, create_queue_embed, forget_queue_message,
         },
         voteskip::forget_skip_votes,
     },
-    guild::{cache::GuildCacheMap, settings::GuildSettingsMap, stored_queue::GuildStoredQueueMap},
+    guild::{
+        cache::GuildCacheMap, persisted_queue::PersistedTrack, settings::GuildSettingsMap,
+        stored_queue::GuildStoredQueueMap,
+    },
+    utils::ReplacedTypeMapKey,
 };
 
 pub struct TrackEndHandler {
@@ -35,12 +40,44 @@ pub struct ModifyQueueHandler {
 
 #[async_trait]
 impl EventHandler for TrackEndHandler {
-    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        // tracks recreated with new playback settings haven't really ended
+        if let EventContext::Track(track_list) = ctx {
+            for (_, track) in track_list.iter() {
+                if track
+                    .typemap()
+                    .read()
+                    .await
+                    .contains_key::<ReplacedTypeMapKey>()
+                {
+                    return None;
+                }
+            }
+        }
+
+        if let EventContext::Track(track_list) = ctx {
+            for (state, track) in track_list.iter() {
+                // queued tracks that never started also end when they're removed or stopped
+                if state.play_time.is_zero() {
+                    continue;
+                }
+
+                if let Some(played_track) = PersistedTrack::from_track(track).await {
+                    let mut data = self.ctx_data.write().await;
+                    let cache_map = data.get_mut::<GuildCacheMap>()?;
+                    cache_map
+                        .entry(self.guild_id)
+                        .or_default()
+                        .push_history(played_track);
+                }
+            }
+        }
+
         let data_rlock = self.ctx_data.read().await;
-        let (autopause, queue_loop) = data_rlock
+        let (autopause, queue_loop, autoplay) = data_rlock
             .get::<GuildSettingsMap>()?
             .get(&self.guild_id)
-            .map(|setting| (setting.autopause, setting.queue_loop))
+            .map(|setting| (setting.autopause, setting.queue_loop, setting.autoplay))
             .unwrap_or_default();
         let guild_stored_queue = data_rlock
             .get::<GuildStoredQueueMap>()?
@@ -60,7 +97,7 @@ impl EventHandler for TrackEndHandler {
             drop(handler);
 
             if is_queue_empty {
-                for item in guild_stored_queue.queue {
+                for (item, requester) in guild_stored_queue.queue {
                     if let Err(err) = normal_query_type_resolver(
                         &self.call,
                         &self.http,
@@ -68,6 +105,7 @@ impl EventHandler for TrackEndHandler {
                         self.guild_id,
                         &item,
                         Mode::End,
+                        requester,
                     )
                     .await
                     {
@@ -75,6 +113,20 @@ impl EventHandler for TrackEndHandler {
                     }
                 }
             }
+        } else if autoplay && guild_stored_queue.continue_play {
+            let handler = self.call.lock().await;
+            let is_queue_empty = handler.queue().is_empty();
+            drop(handler);
+
+            // the track that just ended was pushed to the history above and seeds the next one
+            if is_queue_empty {
+                if let Err(err) =
+                    enqueue_related_track(&self.call, &self.http, &self.ctx_data, self.guild_id)
+                        .await
+                {
+                    println!("{}", err);
+                }
+            }
         }
 
         forget_skip_votes(&self.ctx_data, self.guild_id).await.ok();
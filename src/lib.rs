@@ -2,6 +2,7 @@ pub mod client;
 pub mod commands;
 pub mod connection;
 pub mod errors;
+pub mod filters;
 pub mod guild;
 pub mod handlers;
 pub mod messaging;
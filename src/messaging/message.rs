@@ -2,7 +2,13 @@ use std::fmt::Display;
 
 use serenity::model::mention::Mention;
 
-use crate::messaging::messages::*;
+use crate::{
+    filters::format_playback_rate,
+    guild::settings::{DuplicatePolicy, QueueLimit},
+    messaging::messages::*,
+    utils::get_human_readable_timestamp,
+};
+use std::time::Duration;
 
 const RELEASES_LINK: &str = "https://github.com/aquelemiguel/parrot/releases";
 
@@ -10,28 +16,152 @@ const RELEASES_LINK: &str = "https://github.com/aquelemiguel/parrot/releases";
 pub enum ParrotMessage {
     AutopauseOff,
     AutopauseOn,
+    AutoplayOff,
+    AutoplayOn,
+    Chapter {
+        title: String,
+    },
     Clear,
+    Crossfade {
+        seconds: u8,
+    },
+    CrossfadeCurrent {
+        seconds: u8,
+    },
+    CrossfadeOff,
+    Duplicates {
+        policy: DuplicatePolicy,
+    },
     Error,
+    FairQueueOff,
+    FairQueueOn,
+    Filters {
+        filters: String,
+    },
+    FiltersOff,
     Leaving,
+    LibraryScanned {
+        count: usize,
+    },
+    LibraryScanning,
+    LibraryStats {
+        tracks: usize,
+        artists: usize,
+        albums: usize,
+        duration: Duration,
+    },
+    Limits {
+        queue_length: Option<usize>,
+        tracks_per_user: Option<usize>,
+        playlist_size: Option<usize>,
+        track_duration: Option<Duration>,
+    },
     LoopDisable,
     LoopEnable,
+    LoopSection {
+        start: String,
+        end: String,
+    },
+    LoopSectionOff,
+    Move {
+        title: String,
+        url: String,
+        position: usize,
+    },
+    NormalizeOff,
+    NormalizeOn,
     NowPlaying,
     Pause,
+    Pitch {
+        pitch: f64,
+    },
     PlayAllFailed,
-    PlayDomainBanned { domain: String },
+    PlayDomainBanned {
+        domain: String,
+    },
+    PlaylistDeleted {
+        name: String,
+    },
+    PlaylistLoaded {
+        name: String,
+        count: usize,
+    },
     PlaylistQueued,
+    PlaylistSaved {
+        name: String,
+        count: usize,
+    },
+    PlaylistTrackAdded {
+        title: String,
+        url: String,
+        name: String,
+    },
+    PlaylistPartial {
+        limit: Option<QueueLimit>,
+        duplicates: usize,
+        blocked: usize,
+    },
+    Previous {
+        title: String,
+        url: String,
+    },
+    RemoveDuplicates {
+        count: usize,
+    },
+    RemoveGone {
+        count: usize,
+    },
     RemoveMultiple,
+    RemoveUser {
+        count: usize,
+        mention: Mention,
+    },
+    QueueExported {
+        count: usize,
+    },
+    QueueImported {
+        count: usize,
+        skipped: usize,
+        limit: Option<QueueLimit>,
+    },
     Resume,
     Search,
-    Seek { timestamp: String },
+    Seek {
+        timestamp: String,
+    },
     Shuffle,
     Skip,
     SkipAll,
-    SkipTo { title: String, url: String },
+    SkipTo {
+        title: String,
+        url: String,
+    },
+    Speed {
+        speed: f64,
+    },
+    SponsorBlockOff,
+    SponsorBlockOn,
     Stop,
-    Summon { mention: Mention },
-    Version { current: String },
-    VoteSkip { mention: Mention, missing: usize },
+    Summon {
+        mention: Mention,
+    },
+    Swap {
+        first: String,
+        second: String,
+    },
+    Version {
+        current: String,
+    },
+    Volume {
+        volume: u8,
+    },
+    VolumeCurrent {
+        volume: u8,
+    },
+    VoteSkip {
+        mention: Mention,
+        missing: usize,
+    },
 }
 
 impl Display for ParrotMessage {
@@ -39,20 +169,184 @@ impl Display for ParrotMessage {
         match self {
             Self::AutopauseOff => f.write_str(AUTOPAUSE_OFF),
             Self::AutopauseOn => f.write_str(AUTOPAUSE_ON),
+            Self::AutoplayOff => f.write_str(AUTOPLAY_OFF),
+            Self::AutoplayOn => f.write_str(AUTOPLAY_ON),
+            Self::Chapter { title } => f.write_str(&format!("{} **{}**!", CHAPTER, title)),
             Self::Clear => f.write_str(CLEARED),
+            Self::Crossfade { seconds } => {
+                f.write_str(&format!("{} **{}s**!", CROSSFADE_SET, seconds))
+            }
+            Self::CrossfadeCurrent { seconds } => {
+                f.write_str(&format!("{} **{}s**!", CROSSFADE_CURRENT, seconds))
+            }
+            Self::CrossfadeOff => f.write_str(CROSSFADE_OFF),
+            Self::Duplicates { policy } => match policy {
+                DuplicatePolicy::Allow => f.write_str(DUPLICATES_ALLOW),
+                DuplicatePolicy::Reject => f.write_str(DUPLICATES_REJECT),
+                DuplicatePolicy::RejectWithin(n) => f.write_str(&format!(
+                    "{} **{}** {}",
+                    DUPLICATES_REJECT_WITHIN, n, DUPLICATES_REJECT_WITHIN_TRACKS
+                )),
+            },
             Self::Error => f.write_str(ERROR),
+            Self::FairQueueOff => f.write_str(FAIR_QUEUE_OFF),
+            Self::FairQueueOn => f.write_str(FAIR_QUEUE_ON),
+            Self::Filters { filters } => f.write_str(&format!("{} **{}**", FILTERS_ON, filters)),
+            Self::FiltersOff => f.write_str(FILTERS_OFF),
             Self::Leaving => f.write_str(LEAVING),
+            Self::LibraryScanned { count } => f.write_str(&format!(
+                "{} **{}** {}",
+                LIBRARY_SCANNED, count, LIBRARY_SCANNED_TRACKS
+            )),
+            Self::LibraryScanning => f.write_str(LIBRARY_SCANNING),
+            Self::LibraryStats {
+                tracks,
+                artists,
+                albums,
+                duration,
+            } => f.write_str(&format!(
+                "**{}**\n{} **{}**\n{} **{}**\n{} **{}**\n{} **{}**",
+                LIBRARY_TITLE,
+                LIBRARY_TRACKS,
+                tracks,
+                LIBRARY_ARTISTS,
+                artists,
+                LIBRARY_ALBUMS,
+                albums,
+                LIBRARY_DURATION,
+                get_human_readable_timestamp(Some(*duration)),
+            )),
+            Self::Limits {
+                queue_length,
+                tracks_per_user,
+                playlist_size,
+                track_duration,
+            } => {
+                let format_limit = |limit: Option<String>| limit.unwrap_or(LIMIT_NONE.to_string());
+                f.write_str(&format!(
+                    "**{}**\n{} **{}**\n{} **{}**\n{} **{}**\n{} **{}**",
+                    LIMITS_TITLE,
+                    LIMIT_QUEUE_LENGTH,
+                    format_limit(queue_length.map(|max| max.to_string())),
+                    LIMIT_TRACKS_PER_USER,
+                    format_limit(tracks_per_user.map(|max| max.to_string())),
+                    LIMIT_PLAYLIST_SIZE,
+                    format_limit(playlist_size.map(|max| max.to_string())),
+                    LIMIT_TRACK_DURATION,
+                    format_limit(track_duration.map(|max| get_human_readable_timestamp(Some(max)))),
+                ))
+            }
             Self::LoopDisable => f.write_str(LOOP_DISABLED),
             Self::LoopEnable => f.write_str(LOOP_ENABLED),
+            Self::LoopSection { start, end } => {
+                f.write_str(&format!("{} **{}** to **{}**!", LOOP_SECTION, start, end))
+            }
+            Self::LoopSectionOff => f.write_str(LOOP_SECTION_OFF),
+            Self::Move {
+                title,
+                url,
+                position,
+            } => f.write_str(&format!(
+                "{} [**{}**]({}) to position **{}**!",
+                MOVED, title, url, position
+            )),
+            Self::NormalizeOff => f.write_str(NORMALIZE_OFF),
+            Self::NormalizeOn => f.write_str(NORMALIZE_ON),
             Self::NowPlaying => f.write_str(QUEUE_NOW_PLAYING),
             Self::Pause => f.write_str(PAUSED),
+            Self::Pitch { pitch } => f.write_str(&format!(
+                "{} **{}**!",
+                PITCH_SET,
+                format_playback_rate(*pitch)
+            )),
+            Self::PlaylistDeleted { name } => {
+                f.write_str(&format!("{} **{}**!", PLAYLIST_DELETED, name))
+            }
+            Self::PlaylistLoaded { name, count } => f.write_str(&format!(
+                "{} **{}** {} **{}**!",
+                PLAYLIST_LOADED, count, PLAYLIST_LOADED_FROM, name
+            )),
             Self::PlaylistQueued => f.write_str(PLAY_PLAYLIST),
+            Self::PlaylistSaved { name, count } => f.write_str(&format!(
+                "{} **{}** {} **{}**!",
+                PLAYLIST_SAVED, count, PLAYLIST_SAVED_TO, name
+            )),
+            Self::PlaylistTrackAdded { title, url, name } => f.write_str(&format!(
+                "{} [**{}**]({}) {} **{}**!",
+                PLAYLIST_ADDED, title, url, PLAYLIST_ADDED_TO, name
+            )),
+            Self::PlaylistPartial {
+                limit,
+                duplicates,
+                blocked,
+            } => {
+                f.write_str(PLAY_PLAYLIST_PARTIAL)?;
+
+                if *blocked > 0 {
+                    f.write_str(&format!(
+                        "\n{} **{}** {}",
+                        PLAY_PLAYLIST_DUPLICATES, blocked, PLAY_PLAYLIST_BLOCKED
+                    ))?;
+                }
+
+                if *duplicates > 0 {
+                    f.write_str(&format!(
+                        "\n{} **{}** {}",
+                        PLAY_PLAYLIST_DUPLICATES, duplicates, PLAY_PLAYLIST_DUPLICATES_QUEUED
+                    ))?;
+                }
+
+                match limit {
+                    Some(limit) => f.write_str(&format!("\n{}", limit)),
+                    None => Ok(()),
+                }
+            }
+            Self::Previous { title, url } => {
+                f.write_str(&format!("{} [**{}**]({})!", PREVIOUS, title, url))
+            }
             Self::PlayAllFailed => f.write_str(PLAY_ALL_FAILED),
             Self::PlayDomainBanned { domain } => {
                 f.write_str(&format!("⚠️ **{}** {}", domain, PLAY_FAILED_BLOCKED_DOMAIN))
             }
+            Self::QueueExported { count } => f.write_str(&format!(
+                "{} **{}** {}",
+                QUEUE_EXPORTED, count, QUEUE_EXPORTED_TRACKS
+            )),
+            Self::QueueImported {
+                count,
+                skipped,
+                limit,
+            } => {
+                f.write_str(&format!(
+                    "{} **{}** {}",
+                    QUEUE_IMPORTED, count, QUEUE_IMPORTED_TRACKS
+                ))?;
+
+                if *skipped > 0 {
+                    f.write_str(&format!(
+                        "\n{} **{}** {}",
+                        PLAY_PLAYLIST_DUPLICATES, skipped, QUEUE_IMPORTED_SKIPPED
+                    ))?;
+                }
+
+                match limit {
+                    Some(limit) => f.write_str(&format!("\n{}", limit)),
+                    None => Ok(()),
+                }
+            }
             Self::Search => f.write_str(SEARCHING),
+            Self::RemoveDuplicates { count } => f.write_str(&format!(
+                "{} **{}** {}",
+                REMOVED_COUNT, count, REMOVED_DUPLICATES
+            )),
+            Self::RemoveGone { count } => {
+                f.write_str(&format!("{} **{}** {}", REMOVED_COUNT, count, REMOVED_GONE))
+            }
             Self::RemoveMultiple => f.write_str(REMOVED_QUEUE_MULTIPLE),
+            Self::RemoveUser { count, mention } => f.write_str(&format!(
+                "{} **{}** {} {}!",
+                REMOVED_COUNT, count, REMOVED_USER, mention
+            )),
             Self::Resume => f.write_str(RESUMED),
             Self::Shuffle => f.write_str(SHUFFLED_SUCCESS),
             Self::Stop => f.write_str(STOPPED),
@@ -66,11 +360,47 @@ impl Display for ParrotMessage {
             Self::SkipTo { title, url } => {
                 f.write_str(&format!("{} [**{}**]({})!", SKIPPED_TO, title, url))
             }
+            Self::Speed { speed } => f.write_str(&format!(
+                "{} **{}**!",
+                SPEED_SET,
+                format_playback_rate(*speed)
+            )),
+            Self::SponsorBlockOff => f.write_str(SPONSORBLOCK_OFF),
+            Self::SponsorBlockOn => f.write_str(SPONSORBLOCK_ON),
             Self::Summon { mention } => f.write_str(&format!("{} **{}**!", JOINING, mention)),
+            Self::Swap { first, second } => {
+                f.write_str(&format!("{} **{}** and **{}**!", SWAPPED, first, second))
+            }
             Self::Version { current } => f.write_str(&format!(
                 "{} [{}]({}/tag/v{})\n{}({}/latest)",
                 VERSION, current, RELEASES_LINK, current, VERSION_LATEST, RELEASES_LINK
             )),
+            Self::Volume { volume } => f.write_str(&format!("{} **{}%**!", VOLUME_SET, volume)),
+            Self::VolumeCurrent { volume } => {
+                f.write_str(&format!("{} **{}%**", VOLUME_CURRENT, volume))
+            }
+        }
+    }
+}
+
+impl Display for QueueLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::QueueLength(max) => {
+                f.write_str(&format!("{} **{}** tracks.", FAIL_LIMIT_QUEUE_LENGTH, max))
+            }
+            Self::TracksPerUser(max) => f.write_str(&format!(
+                "{} **{}** queued tracks per person!",
+                FAIL_LIMIT_TRACKS_PER_USER, max
+            )),
+            Self::PlaylistSize(max) => {
+                f.write_str(&format!("{} **{}** tracks!", FAIL_LIMIT_PLAYLIST_SIZE, max))
+            }
+            Self::TrackDuration(max) => f.write_str(&format!(
+                "{} **{}** long.",
+                FAIL_LIMIT_TRACK_DURATION,
+                get_human_readable_timestamp(Some(*max))
+            )),
         }
     }
 }
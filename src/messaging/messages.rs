@@ -1,6 +1,12 @@
 pub const AUTOPAUSE_OFF: &str = "🤖 Autopause OFF!";
 pub const AUTOPAUSE_ON: &str = "🤖 Autopause ON!";
+pub const AUTOPLAY_OFF: &str = "📻 Autoplay OFF!";
+pub const AUTOPLAY_ON: &str = "📻 Autoplay ON!";
+pub const CHAPTER: &str = "📑 Jumped to chapter";
 pub const CLEARED: &str = "🗑️ Cleared!";
+pub const CROSSFADE_CURRENT: &str = "🎶 Crossfade is currently at";
+pub const CROSSFADE_OFF: &str = "🎶 Disabled crossfade!";
+pub const CROSSFADE_SET: &str = "🎶 Crossfade set to";
 
 pub const DOMAIN_FORM_ALLOWED_TITLE: &str = "Allowed domains";
 pub const DOMAIN_FORM_BANNED_TITLE: &str = "Banned domains";
@@ -10,34 +16,122 @@ pub const DOMAIN_FORM_BANNED_PLACEHOLDER: &str =
     "Add domains separated by \';\'. If left blank, all (except for allowed) are blocked by default.";
 pub const DOMAIN_FORM_TITLE: &str = "Manage sources";
 
+pub const DUPLICATES_ALLOW: &str = "🔂 Tracks can be queued more than once!";
+pub const DUPLICATES_REJECT: &str = "🔂 Tracks that are already queued will be rejected!";
+pub const DUPLICATES_REJECT_WITHIN: &str = "🔂 Tracks already among the last";
+pub const DUPLICATES_REJECT_WITHIN_TRACKS: &str = "queued tracks will be rejected!";
 pub const ERROR: &str = "Fatality! Something went wrong ☹️";
 pub const FAIL_ALREADY_HERE: &str = "⚠️ I'm already here!";
 pub const FAIL_ANOTHER_CHANNEL: &str = "⚠️ I'm already connected to";
+pub const FAIL_ATTACHMENT_NOT_MEDIA: &str = "⚠️ Only audio and video files can be played!";
+pub const FAIL_ATTACHMENT_TOO_LARGE: &str = "⚠️ That file is too large to play!";
+pub const FAIL_ATTACHMENT_UNSUPPORTED: &str =
+    "⚠️ I couldn't read that file! Try an MP3, M4A, FLAC, OGG or WAV file.";
 pub const FAIL_AUTHOR_DISCONNECTED: &str = "⚠️ You are not connected to";
 pub const FAIL_AUTHOR_NOT_FOUND: &str = "⚠️ Could not find you in any voice channel!";
+pub const FAIL_AUTOPLAY_NO_RELATED: &str =
+    "⚠️ Couldn't find a related track that wasn't played recently!";
+pub const FAIL_AUTOPLAY_NO_SEED: &str = "⚠️ There's no played track to base autoplay on!";
+pub const FAIL_CHAPTER_NOT_FOUND: &str = "⚠️ There's no such chapter in this track!";
+pub const FAIL_DUPLICATE_TRACK: &str = "⚠️ That track is already in the queue!";
+pub const FAIL_INVALID_FILTER: &str = "⚠️ That filter doesn't exist!";
+pub const FAIL_INVALID_LOOP_SECTION: &str =
+    "⚠️ The section needs to end after it starts and before the track does!";
+pub const FAIL_INVALID_TIMESTAMP: &str =
+    "⚠️ Invalid timestamp! Try something like `1:23`, `1h2m` or `+30s`.";
+pub const FAIL_LIBRARY_DISABLED: &str = "⚠️ There's no music library set up for me!";
+pub const FAIL_LIBRARY_NO_MATCH: &str = "⚠️ Nothing in the library matches that!";
+pub const FAIL_LIBRARY_SCANNING: &str = "⚠️ The library is already being scanned!";
+pub const FAIL_LIMIT_PLAYLIST_SIZE: &str = "⚠️ Playlists can only add up to";
+pub const FAIL_LIMIT_QUEUE_LENGTH: &str = "⚠️ The queue is full! It can hold up to";
+pub const FAIL_LIMIT_TRACK_DURATION: &str = "⚠️ That track is too long! Tracks can be up to";
+pub const FAIL_LIMIT_TRACKS_PER_USER: &str = "⚠️ You've reached the limit of";
 pub const FAIL_LOOP: &str = "⚠️ Failed to toggle loop!";
-pub const FAIL_MINUTES_PARSING: &str = "⚠️ Invalid formatting for 'minutes'";
+pub const FAIL_NO_CHAPTERS: &str = "⚠️ This track has no chapters!";
+pub const FAIL_NO_LOOP_SECTION: &str = "⚠️ No section of this track is being looped!";
+pub const FAIL_NO_PREVIOUS_TRACK: &str = "⚠️ There's no previous track to go back to!";
 pub const FAIL_NO_SONG_ON_INDEX: &str = "⚠️ There is no queued song on that index!";
 pub const FAIL_NO_VOICE_CONNECTION: &str = "⚠️ I'm not connected to any voice channel!";
+pub const FAIL_PLAYLIST_NOT_FOUND: &str = "⚠️ There's no saved playlist with that name!";
+pub const FAIL_PLAYLIST_NOT_TRACK: &str = "⚠️ Only single tracks can be added to a playlist!";
+pub const FAIL_PLAYLIST_PERMISSION: &str =
+    "⚠️ You need the Manage Server permission to change this server's playlists!";
+pub const FAIL_PLAY_NO_QUERY: &str =
+    "⚠️ Tell me what to play, with a query, a file or a library search!";
+pub const FAIL_QUEUE_IMPORT_EMPTY: &str = "⚠️ That file doesn't list any tracks!";
+pub const FAIL_QUEUE_IMPORT_TOO_LARGE: &str = "⚠️ That file is too large to import!";
+pub const FAIL_RADIO_EMPTY: &str = "⚠️ That playlist doesn't list any streams!";
+pub const FAIL_RADIO_INVALID_PLAYLIST: &str = "⚠️ That doesn't look like a radio playlist!";
+pub const FAIL_RADIO_NOT_AUDIO: &str = "⚠️ That radio station doesn't stream any audio!";
+pub const FAIL_RADIO_UNREACHABLE: &str = "⚠️ Couldn't connect to that radio station!";
 pub const FAIL_REMOVE_RANGE: &str = "⚠️ `until` needs to be higher than `index`!";
-pub const FAIL_SECONDS_PARSING: &str = "⚠️ Invalid formatting for 'seconds'";
+pub const FAIL_SEEK_OUT_OF_BOUNDS: &str = "⚠️ That's past the end of the track!";
+pub const FAIL_SWAP_NOT_STORED: &str =
+    "⚠️ Only one of those tracks comes back when the queue loops, so they can't be swapped!";
 pub const FAIL_WRONG_CHANNEL: &str = "⚠️ We are not in the same voice channel!";
+pub const FAIR_QUEUE_OFF: &str = "⚖️ Fair queue OFF!";
+pub const FAIR_QUEUE_ON: &str = "⚖️ Fair queue ON! Everyone's tracks now take turns.";
+pub const FILTERS_OFF: &str = "🎛️ Disabled all filters!";
+pub const FILTERS_ON: &str = "🎛️ Active filters:";
+pub const HISTORY_EMPTY: &str = "Nothing has been played yet!";
+pub const HISTORY_TITLE: &str = "🕘 Recently played";
 pub const IDLE_ALERT: &str = "I've been idle for a while, so I'll leave for now to save resources.\nFeel free to summon me back any time!";
 pub const JOINING: &str = "Joining";
 pub const LEAVING: &str = "👋 See you soon!";
+pub const LIBRARY_ALBUMS: &str = "Albums:";
+pub const LIBRARY_ARTISTS: &str = "Artists:";
+pub const LIBRARY_DURATION: &str = "Total duration:";
+pub const LIBRARY_SCANNED: &str = "📚 Indexed";
+pub const LIBRARY_SCANNED_TRACKS: &str = "track(s) in the library!";
+pub const LIBRARY_SCANNING: &str = "📚 Scanning the library...";
+pub const LIBRARY_TITLE: &str = "📚 Library";
+pub const LIBRARY_TRACKS: &str = "Tracks:";
+pub const LIMIT_NONE: &str = "none";
+pub const LIMIT_PLAYLIST_SIZE: &str = "Tracks per playlist:";
+pub const LIMIT_QUEUE_LENGTH: &str = "Tracks in queue:";
+pub const LIMIT_TRACK_DURATION: &str = "Track duration:";
+pub const LIMIT_TRACKS_PER_USER: &str = "Tracks per person:";
+pub const LIMITS_TITLE: &str = "🚦 Queue limits";
 pub const LOOP_DISABLED: &str = "🔁 Disabled loop!";
 pub const LOOP_ENABLED: &str = "🔁 Enabled loop!";
+pub const LOOP_SECTION: &str = "🔁 Looping the section from";
+pub const LOOP_SECTION_OFF: &str = "🔁 Stopped looping the section!";
+pub const MOVED: &str = "↕️ Moved";
+pub const NORMALIZE_OFF: &str = "🎚️ Loudness normalization OFF!";
+pub const NORMALIZE_ON: &str = "🎚️ Loudness normalization ON!";
 pub const NOTHING_IS_PLAYING: &str = "🔈 Nothing is playing!";
 pub const PAUSED: &str = "⏸️ Paused!";
+pub const PITCH_SET: &str = "🎵 Pitch set to";
+pub const PLAYLIST_ADDED: &str = "💾 Added";
+pub const PLAYLIST_ADDED_TO: &str = "to playlist";
+pub const PLAYLIST_DELETED: &str = "🗑️ Deleted playlist";
+pub const PLAYLIST_LOADED: &str = "📃 Loaded";
+pub const PLAYLIST_LOADED_FROM: &str = "track(s) from playlist";
+pub const PLAYLIST_SAVED: &str = "💾 Saved";
+pub const PLAYLIST_SAVED_TO: &str = "track(s) to playlist";
+pub const PLAYLIST_TRACKS: &str = "track(s)";
+pub const PLAYLISTS_EMPTY: &str = "No playlists saved yet!";
+pub const PLAYLISTS_GUILD_TITLE: &str = "💾 Server playlists";
+pub const PLAYLISTS_USER_TITLE: &str = "💾 Your playlists";
 pub const PLAY_FAILED_BLOCKED_DOMAIN: &str =
     "**is either not allowed in this server or is not supported!** \n\nTo explicitely allow this domain, ask a moderator to run the `/managesources` command. [Click to see a list of supported sources.](https://github.com/yt-dlp/yt-dlp/blob/master/supportedsites.md)";
 pub const PLAY_ALL_FAILED: &str =
     "⚠️ Cannot fetch playlist via keywords! Try passing this command an URL.";
 pub const PLAY_PLAYLIST: &str = "📃 Added playlist to queue!";
+pub const PLAY_PLAYLIST_BLOCKED: &str = "track(s) from domains this server doesn't allow.";
+pub const PLAY_PLAYLIST_DUPLICATES: &str = "⏭️ Skipped";
+pub const PLAY_PLAYLIST_DUPLICATES_QUEUED: &str = "track(s) that were already queued.";
+pub const PLAY_PLAYLIST_PARTIAL: &str = "📃 Added part of the playlist to queue!";
 pub const PLAY_QUEUE: &str = "📃 Added to queue!";
 pub const PLAY_TOP: &str = "📃 Added to top!";
+pub const PREVIOUS: &str = "⏮️ Went back to";
 pub const QUEUE_EXPIRED: &str =
     "In order to save resources, this command has expired.\nPlease feel free to reinvoke it!";
+pub const QUEUE_EXPORTED: &str = "📤 Exported";
+pub const QUEUE_EXPORTED_TRACKS: &str = "track(s) from the queue!";
+pub const QUEUE_IMPORTED: &str = "📥 Imported";
+pub const QUEUE_IMPORTED_SKIPPED: &str = "entries that are blocked, already queued or unavailable.";
+pub const QUEUE_IMPORTED_TRACKS: &str = "track(s) to the queue!";
 pub const QUEUE_IS_EMPTY: &str = "Queue is empty!";
 pub const QUEUE_NO_SONGS: &str = "There's no songs up next!";
 pub const QUEUE_NOTHING_IS_PLAYING: &str = "Nothing is playing!";
@@ -45,8 +139,12 @@ pub const QUEUE_NOW_PLAYING: &str = "🔊 Now playing";
 pub const QUEUE_PAGE_OF: &str = "of";
 pub const QUEUE_PAGE: &str = "Page";
 pub const QUEUE_UP_NEXT: &str = "⌛ Up next";
+pub const REMOVED_COUNT: &str = "❌ Removed";
+pub const REMOVED_DUPLICATES: &str = "duplicate track(s) from queue!";
+pub const REMOVED_GONE: &str = "track(s) requested by people who left the voice channel!";
 pub const REMOVED_QUEUE_MULTIPLE: &str = "❌ Removed multiple tracks from queue!";
 pub const REMOVED_QUEUE: &str = "❌ Removed from queue";
+pub const REMOVED_USER: &str = "track(s) requested by";
 pub const RESUMED: &str = "▶️ Resumed!";
 pub const SEARCHING: &str = "🔎 Searching...";
 pub const SEEKED: &str = "⏩ Seeked current track to";
@@ -57,14 +155,22 @@ pub const SKIP_VOTE_USER: &str = "has voted to skip!";
 pub const SKIPPED_ALL: &str = "⏭️ Skipped until infinity!";
 pub const SKIPPED_TO: &str = "⏭️ Skipped to";
 pub const SKIPPED: &str = "⏭️ Skipped!";
+pub const SPEED_SET: &str = "⏩ Playback speed set to";
+pub const SPONSORBLOCK_OFF: &str = "⏭️ Skipping sponsored segments OFF!";
+pub const SPONSORBLOCK_ON: &str = "⏭️ Skipping sponsored segments ON!";
 pub const SPOTIFY_AUTH_FAILED: &str = "⚠️ **Could not authenticate with Spotify!**\nDid you forget to provide your Spotify application's client ID and secret?";
 pub const SPOTIFY_INVALID_QUERY: &str =
     "⚠️ **Could not find any tracks with that link!**\nAre you sure that is a valid Spotify URL?";
 pub const SPOTIFY_PLAYLIST_FAILED: &str = "⚠️ **Failed to fetch playlist!**\nIt's likely that this playlist is either private or a personalized recommendation playlist generated by Spotify.";
 pub const STOPPED: &str = "⏹️ Stopped!";
+pub const SWAPPED: &str = "🔃 Swapped";
 pub const TRACK_DURATION: &str = "Track duration: ";
 pub const TRACK_NOT_FOUND: &str = "⚠️ **Could not play track!**\nYour request yielded no results.";
 pub const TRACK_INAPPROPRIATE: &str = "⚠️ **Could not play track!**\nThe video you requested may be inappropriate for some users, so sign-in is required.";
+pub const TRACK_REQUESTED_BY: &str = "Requested by";
+pub const TRACK_START_OFFSET: &str = "Starting at: ";
 pub const TRACK_TIME_TO_PLAY: &str = "Estimated time until play: ";
 pub const VERSION_LATEST: &str = "Find the latest version [here]";
 pub const VERSION: &str = "Version";
+pub const VOLUME_CURRENT: &str = "🔊 Volume is currently at";
+pub const VOLUME_SET: &str = "🔊 Volume set to";
@@ -0,0 +1,183 @@
+use lazy_static::lazy_static;
+use songbird::input::AuxMetadata;
+use std::{env, io::Cursor, path::Path, time::Duration};
+use symphonia::core::{
+    codecs::{CodecParameters, CODEC_TYPE_NULL},
+    formats::{FormatOptions, FormatReader},
+    io::{MediaSource, MediaSourceStream},
+    meta::{MetadataOptions, StandardTagKey, Tag},
+    probe::Hint,
+    units::{Time, TimeBase},
+};
+use url::Url;
+
+use crate::{
+    errors::{verify, ParrotError},
+    messaging::messages::{FAIL_ATTACHMENT_TOO_LARGE, FAIL_ATTACHMENT_UNSUPPORTED},
+};
+
+// the upload limit for members without Nitro, in megabytes
+const DEFAULT_MAX_ATTACHMENT_SIZE: u64 = 25;
+
+lazy_static! {
+    pub static ref MAX_ATTACHMENT_SIZE: u64 = env::var("MAX_ATTACHMENT_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(DEFAULT_MAX_ATTACHMENT_SIZE)
+        * 1024
+        * 1024;
+}
+
+pub struct AttachmentSource {}
+
+impl AttachmentSource {
+    /// Whether the link points at a file uploaded to Discord rather than a page yt-dlp can scrape.
+    pub fn is_attachment_url(url: &str) -> bool {
+        Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(ToOwned::to_owned))
+            .map_or(false, |host| {
+                host == "cdn.discordapp.com" || host == "media.discordapp.net"
+            })
+    }
+
+    /// Downloads an uploaded file and reads its metadata, refusing files over the size limit.
+    pub async fn fetch_metadata(url: &str) -> Result<AuxMetadata, ParrotError> {
+        let mut response = reqwest::get(url)
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|_| ParrotError::Other("failed to download the attachment"))?;
+
+        let is_too_large = response
+            .content_length()
+            .map_or(false, |size| size > *MAX_ATTACHMENT_SIZE);
+        verify(!is_too_large, ParrotError::Other(FAIL_ATTACHMENT_TOO_LARGE))?;
+
+        // read in chunks, since the size isn't always announced up front
+        let mut bytes = Vec::new();
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|_| ParrotError::Other("failed to download the attachment"))?
+        {
+            bytes.extend_from_slice(&chunk);
+            verify(
+                bytes.len() as u64 <= *MAX_ATTACHMENT_SIZE,
+                ParrotError::Other(FAIL_ATTACHMENT_TOO_LARGE),
+            )?;
+        }
+
+        let file_name = Url::parse(url)
+            .ok()
+            .and_then(|url| url.path_segments()?.last().map(ToOwned::to_owned))
+            .unwrap_or_default();
+
+        // probing may have to read through the whole file, which would block the runtime
+        let metadata = tokio::task::spawn_blocking(move || {
+            probe_metadata(Box::new(Cursor::new(bytes)), &file_name)
+        })
+        .await
+        .ok()
+        .flatten();
+
+        let mut metadata = verify(metadata, ParrotError::Other(FAIL_ATTACHMENT_UNSUPPORTED))?;
+        metadata.source_url = Some(url.to_string());
+        Ok(metadata)
+    }
+}
+
+/// Reads the tags and audio properties of a media file, or `None` if it can't be decoded.
+pub fn probe_metadata(source: Box<dyn MediaSource>, file_name: &str) -> Option<AuxMetadata> {
+    let mut hint = Hint::new();
+    if let Some(extension) = Path::new(file_name).extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let source = MediaSourceStream::new(source, Default::default());
+    let mut probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .ok()?;
+
+    // tags can sit in front of the container, like ID3 in MP3s, or inside it
+    let mut tags: Vec<Tag> = Vec::new();
+    if let Some(revision) = probed.metadata.get().as_ref().and_then(|m| m.current()) {
+        tags.extend_from_slice(revision.tags());
+    }
+    if let Some(revision) = probed.format.metadata().current() {
+        tags.extend_from_slice(revision.tags());
+    }
+
+    // RIFF INFO values keep their NUL terminator
+    let find_tag = |key: StandardTagKey| {
+        tags.iter()
+            .filter(|tag| tag.std_key == Some(key))
+            .map(|tag| {
+                tag.value
+                    .to_string()
+                    .trim_matches(char::from(0))
+                    .trim()
+                    .to_string()
+            })
+            .find(|value| !value.is_empty())
+    };
+
+    // videos carry other tracks alongside the audio
+    let track = probed
+        .format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)?;
+    let track_id = track.id;
+    let params = track.codec_params.clone();
+
+    let title = find_tag(StandardTagKey::TrackTitle).or_else(|| {
+        Path::new(file_name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+    });
+
+    Some(AuxMetadata {
+        track: find_tag(StandardTagKey::TrackTitle),
+        artist: find_tag(StandardTagKey::Artist),
+        album: find_tag(StandardTagKey::Album),
+        date: find_tag(StandardTagKey::Date),
+        channels: params.channels.map(|channels| channels.count() as u8),
+        sample_rate: params.sample_rate,
+        duration: get_duration(probed.format.as_mut(), track_id, &params),
+        title,
+        ..Default::default()
+    })
+}
+
+fn get_duration(
+    format: &mut dyn FormatReader,
+    track_id: u32,
+    params: &CodecParameters,
+) -> Option<Duration> {
+    let time_base = params
+        .time_base
+        .or_else(|| params.sample_rate.map(|rate| TimeBase::new(1, rate)))?;
+
+    // files without a frame count in their headers have to be read through
+    let frames = match params.n_frames {
+        Some(frames) => frames,
+        None => {
+            let mut end = 0;
+            while let Ok(packet) = format.next_packet() {
+                if packet.track_id() == track_id {
+                    end = end.max(packet.ts() + packet.dur());
+                }
+            }
+            end
+        }
+    };
+
+    let Time { seconds, frac } = time_base.calc_time(frames);
+    Some(Duration::from_secs(seconds) + Duration::from_secs_f64(frac))
+}
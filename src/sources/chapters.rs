@@ -0,0 +1,88 @@
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::errors::ParrotError;
+
+/// A named section of a track, in source time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub start: Duration,
+    pub end: Duration,
+}
+
+#[derive(Deserialize)]
+struct YtDlpOutput {
+    chapters: Option<Vec<YtDlpChapter>>,
+}
+
+#[derive(Deserialize)]
+struct YtDlpChapter {
+    title: String,
+    start_time: f64,
+    end_time: f64,
+}
+
+/// Fetches the chapters yt-dlp reports for the given URL, which songbird doesn't expose.
+pub async fn fetch_chapters(url: &str) -> Result<Vec<Chapter>, ParrotError> {
+    let output = Command::new("yt-dlp")
+        .args([
+            "-j",
+            "--skip-download",
+            "--no-playlist",
+            "--ignore-config",
+            "--no-warnings",
+            url,
+        ])
+        .output()
+        .await?;
+
+    parse_chapters(&String::from_utf8_lossy(&output.stdout))
+}
+
+pub fn parse_chapters(json: &str) -> Result<Vec<Chapter>, ParrotError> {
+    let output: YtDlpOutput = serde_json::from_str(json)?;
+
+    let chapters = output
+        .chapters
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|chapter| chapter.start_time >= 0.0 && chapter.end_time > chapter.start_time)
+        .map(|chapter| Chapter {
+            title: chapter.title,
+            start: Duration::from_secs_f64(chapter.start_time),
+            end: Duration::from_secs_f64(chapter.end_time),
+        })
+        .collect();
+
+    Ok(chapters)
+}
+
+/// The index of the chapter playing at the given source position, if any.
+pub fn find_chapter(chapters: &[Chapter], position: Duration) -> Option<usize> {
+    chapters
+        .iter()
+        .position(|chapter| chapter.start <= position && position < chapter.end)
+}
+
+/// Resolves `next`, `previous` or a chapter's name to the index of the chapter to seek to.
+pub fn select_chapter(chapters: &[Chapter], position: Duration, target: &str) -> Option<usize> {
+    let current = find_chapter(chapters, position);
+
+    match target.to_lowercase().as_str() {
+        "next" => match current {
+            Some(index) => Some(index + 1).filter(|index| *index < chapters.len()),
+            None => chapters.iter().position(|chapter| chapter.start > position),
+        },
+        "previous" => current.map(|index| index.saturating_sub(1)),
+        name => chapters
+            .iter()
+            .position(|chapter| chapter.title.to_lowercase() == name)
+            .or_else(|| {
+                chapters
+                    .iter()
+                    .position(|chapter| chapter.title.to_lowercase().contains(name))
+            }),
+    }
+}
@@ -1,43 +1,266 @@
+use crate::sources::{library::Library, radio::RadioSource};
+use reqwest::Client;
+use serenity::async_trait;
 use songbird::input::{
-    error::{Error, Result},
-    Codec, Container, Input, Metadata, Reader,
+    core::io::MediaSource, AudioStream, AudioStreamError, AuxMetadata, ChildContainer, Compose,
+    Input,
 };
-use std::process::{Child, Command, Stdio};
-
-pub async fn ffmpeg(mut source: Child, metadata: Metadata, pre_args: &[&str]) -> Result<Input> {
-    let ffmpeg_args = [
-        "-i",
-        "-", // read from stdout
-        "-f",
-        "s16le", // use PCM signed 16-bit little-endian format
-        "-ac",
-        "2", // set two audio channels
-        "-ar",
-        "48000", // set audio sample rate of 48000Hz
-        "-acodec",
-        "pcm_f32le",
-        "-",
-    ];
-
-    let taken_stdout = source.stdout.take().ok_or(Error::Stdout)?;
-
-    let ffmpeg = Command::new("ffmpeg")
-        .args(pre_args)
-        .args(ffmpeg_args)
-        .stdin(taken_stdout)
-        .stderr(Stdio::null())
-        .stdout(Stdio::piped())
-        .spawn()?;
-
-    let reader = Reader::from(vec![source, ffmpeg]);
-
-    let input = Input::new(
-        true,
-        reader,
-        Codec::FloatPcm,
-        Container::Raw,
-        Some(metadata),
-    );
-
-    Ok(input)
+use std::{
+    io::{self, ErrorKind, Read, Seek, SeekFrom},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::{Arc, Mutex},
+};
+
+const SAMPLE_RATE: u32 = 48000;
+const CHANNEL_COUNT: u32 = 2;
+
+// header expected by songbird's raw PCM reader: a magic string followed by
+// the sample rate and channel count as little-endian u32s
+const RAW_HEADER_MAGIC: &[u8; 8] = b"SbirdRaw";
+const RAW_HEADER_LEN: usize = 16;
+
+/// ffmpeg's output, along with its stdin when it's to be written to.
+type FfmpegStream = (AudioStream<Box<dyn MediaSource>>, Option<ChildStdin>);
+
+/// A lazily instantiated source which pipes yt-dlp, a library file or a radio
+/// stream through ffmpeg so that an `-af` filter graph can be applied to the audio.
+#[derive(Clone, Debug)]
+pub struct FfmpegSource {
+    url: String,
+    filter_graph: String,
+    metadata: AuxMetadata,
+    stream_title: Arc<Mutex<Option<String>>>,
+}
+
+/// Where ffmpeg reads the audio it filters from.
+enum FfmpegInput {
+    /// A file or link ffmpeg opens by itself.
+    Url(String),
+    /// yt-dlp's download of the track's link.
+    Ytdl,
+    /// Whatever gets written to ffmpeg's stdin.
+    Stdin,
+}
+
+impl FfmpegSource {
+    pub fn new(url: String, filter_graph: String, metadata: AuxMetadata) -> Self {
+        Self {
+            url,
+            filter_graph,
+            metadata,
+            stream_title: Arc::default(),
+        }
+    }
+
+    /// Passes on the song titles of a radio stream, which is read before ffmpeg gets it.
+    pub fn with_stream_title(mut self, stream_title: Option<Arc<Mutex<Option<String>>>>) -> Self {
+        if let Some(stream_title) = stream_title {
+            self.stream_title = stream_title;
+        }
+        self
+    }
+
+    fn spawn_ytdl(&self) -> Result<Child, AudioStreamError> {
+        let ytdl_args = [
+            "-f",
+            "ba[abr>0][vcodec=none]/best", // select best quality audio-only
+            "-q",                          // don't print progress logs (this messes with -o -)
+            "--no-playlist",               // only download the video if URL also has playlist info
+            "--ignore-config",             // disable all configuration files for a yt-dlp run
+            "--no-warnings",               // don't print out warnings
+            &self.url,
+            "-o",
+            "-", // stream data to stdout
+        ];
+
+        Command::new("yt-dlp")
+            .args(ytdl_args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| AudioStreamError::Fail(Box::new(err)))
+    }
+
+    /// Runs ffmpeg on the given input.
+    fn spawn_ffmpeg(&self, input: FfmpegInput) -> Result<FfmpegStream, AudioStreamError> {
+        let (input, stdin, mut children) = match input {
+            FfmpegInput::Url(input) => (input, Stdio::null(), vec![]),
+            FfmpegInput::Stdin => (String::from("-"), Stdio::piped(), vec![]),
+            FfmpegInput::Ytdl => {
+                let mut ytdl = self.spawn_ytdl()?;
+                let taken_stdout = ytdl
+                    .stdout
+                    .take()
+                    .ok_or(AudioStreamError::Fail("yt-dlp has no stdout".into()))?;
+
+                // ffmpeg reads "-" as stdin
+                (String::from("-"), Stdio::from(taken_stdout), vec![ytdl])
+            }
+        };
+
+        let sample_rate = SAMPLE_RATE.to_string();
+        let channel_count = CHANNEL_COUNT.to_string();
+
+        let ffmpeg_args = [
+            "-i",
+            &input,
+            "-af",
+            &self.filter_graph,
+            "-f",
+            "f32le", // raw interleaved 32-bit float samples, as songbird expects
+            "-ac",
+            &channel_count,
+            "-ar",
+            &sample_rate,
+            "-",
+        ];
+
+        let mut ffmpeg = Command::new("ffmpeg")
+            .args(ffmpeg_args)
+            .stdin(stdin)
+            .stderr(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| AudioStreamError::Fail(Box::new(err)))?;
+
+        let stdin = ffmpeg.stdin.take();
+        children.push(ffmpeg);
+        let stream = RawPcmStream::new(ChildContainer::from(children));
+
+        let stream = AudioStream {
+            input: Box::new(stream) as Box<dyn MediaSource>,
+            hint: None,
+        };
+
+        Ok((stream, stdin))
+    }
+}
+
+impl From<FfmpegSource> for Input {
+    fn from(val: FfmpegSource) -> Self {
+        Input::Lazy(Box::new(val))
+    }
+}
+
+#[async_trait]
+impl Compose for FfmpegSource {
+    fn create(&mut self) -> Result<AudioStream<Box<dyn MediaSource>>, AudioStreamError> {
+        // library files are read by ffmpeg itself
+        let input = match Library::resolve(&self.url) {
+            Some(path) => FfmpegInput::Url(path.to_string_lossy().into_owned()),
+            None => FfmpegInput::Ytdl,
+        };
+
+        self.spawn_ffmpeg(input).map(|(stream, _)| stream)
+    }
+
+    async fn create_async(
+        &mut self,
+    ) -> Result<AudioStream<Box<dyn MediaSource>>, AudioStreamError> {
+        // radio playlists have to be resolved to one of their streams first
+        let connection = RadioSource::connect(&Client::new(), &self.url)
+            .await
+            .map_err(|err| AudioStreamError::Fail(err.to_string().into()))?;
+
+        // HLS playlists are the stream themselves, which ffmpeg can follow
+        let Some(connection) = connection else {
+            let input = FfmpegInput::Url(self.url.clone());
+            return self.spawn_ffmpeg(input).map(|(stream, _)| stream);
+        };
+
+        // the stream is read here rather than by ffmpeg, so its song titles aren't lost
+        let (stream, stdin) = self.spawn_ffmpeg(FfmpegInput::Stdin)?;
+        let stdin = stdin
+            .and_then(|stdin| tokio::process::ChildStdin::from_std(stdin).ok())
+            .ok_or(AudioStreamError::Fail("ffmpeg has no stdin".into()))?;
+        connection.forward(stdin, self.stream_title.clone());
+
+        Ok(stream)
+    }
+
+    fn should_create_async(&self) -> bool {
+        RadioSource::is_playlist_url(&self.url)
+    }
+
+    async fn aux_metadata(&mut self) -> Result<AuxMetadata, AudioStreamError> {
+        Ok(self.metadata.clone())
+    }
+}
+
+/// Raw PCM coming out of ffmpeg, prefixed with songbird's raw format header.
+///
+/// Pipes can't seek, but forward seeks are honoured by discarding samples, which
+/// is all songbird needs: backward seeks recreate the source and then skip ahead.
+/// songbird's `RawAdapter` can't stand in for this, since it refuses every seek
+/// unless its source claims to be seekable, and claiming that would stop songbird
+/// from recreating the source for backward seeks.
+struct RawPcmStream {
+    header: [u8; RAW_HEADER_LEN],
+    inner: ChildContainer,
+    pos: u64,
+}
+
+impl RawPcmStream {
+    fn new(inner: ChildContainer) -> Self {
+        let mut header = [0; RAW_HEADER_LEN];
+        header[..8].copy_from_slice(RAW_HEADER_MAGIC);
+        header[8..12].copy_from_slice(&SAMPLE_RATE.to_le_bytes());
+        header[12..].copy_from_slice(&CHANNEL_COUNT.to_le_bytes());
+
+        Self {
+            header,
+            inner,
+            pos: 0,
+        }
+    }
+}
+
+impl Read for RawPcmStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = if self.pos < RAW_HEADER_LEN as u64 {
+            let remaining = &self.header[self.pos as usize..];
+            let len = remaining.len().min(buf.len());
+            buf[..len].copy_from_slice(&remaining[..len]);
+            len
+        } else {
+            self.inner.read(buf)?
+        };
+
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for RawPcmStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(target) => target,
+            SeekFrom::Current(offset) if offset >= 0 => self.pos + offset as u64,
+            _ => return Err(ErrorKind::Unsupported.into()),
+        };
+
+        if target < self.pos {
+            return Err(ErrorKind::Unsupported.into());
+        }
+
+        let to_skip = target - self.pos;
+        let skipped = io::copy(&mut self.by_ref().take(to_skip), &mut io::sink())?;
+
+        if skipped < to_skip {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
+        Ok(self.pos)
+    }
+}
+
+impl MediaSource for RawPcmStream {
+    fn is_seekable(&self) -> bool {
+        false
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        None
+    }
 }
@@ -0,0 +1,385 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use songbird::input::AuxMetadata;
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fs::{self, create_dir_all, File, OpenOptions},
+    io::{BufReader, BufWriter},
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, UNIX_EPOCH},
+};
+use tokio::sync::RwLock;
+
+use crate::{
+    errors::{verify, ParrotError},
+    guild::settings::SETTINGS_PATH,
+    messaging::messages::{FAIL_LIBRARY_DISABLED, FAIL_LIBRARY_NO_MATCH, FAIL_LIBRARY_SCANNING},
+    sources::attachment::probe_metadata,
+};
+
+// links to library tracks are their path inside the library behind this scheme
+const LIBRARY_SCHEME: &str = "library:";
+const AUDIO_EXTENSIONS: [&str; 7] = ["aac", "flac", "m4a", "mp3", "mp4", "ogg", "wav"];
+
+lazy_static! {
+    pub static ref LIBRARY_PATH: Option<PathBuf> = env::var("LIBRARY_PATH").ok().map(PathBuf::from);
+    pub static ref LIBRARY: RwLock<LibraryIndex> = RwLock::new(LibraryIndex::default());
+}
+
+static SCANNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LibraryTrack {
+    pub path: String,
+    pub modified: u64,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+impl LibraryTrack {
+    pub fn source_url(&self) -> String {
+        format!("{}{}", LIBRARY_SCHEME, self.path)
+    }
+
+    pub fn metadata(&self) -> AuxMetadata {
+        AuxMetadata {
+            title: Some(self.display_title()),
+            artist: self.artist.clone(),
+            album: self.album.clone(),
+            duration: self.duration,
+            source_url: Some(self.source_url()),
+            ..Default::default()
+        }
+    }
+
+    /// The track's title tag, falling back to its file name.
+    fn display_title(&self) -> String {
+        self.title.clone().unwrap_or_else(|| {
+            Path::new(&self.path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// What's known about every audio file in the library, kept on disk between restarts.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LibraryIndex {
+    pub tracks: Vec<LibraryTrack>,
+}
+
+pub struct LibraryStats {
+    pub tracks: usize,
+    pub artists: usize,
+    pub albums: usize,
+    pub duration: Duration,
+}
+
+impl LibraryIndex {
+    pub fn load() -> Result<LibraryIndex, ParrotError> {
+        let path = Self::path();
+        if !Path::new(&path).exists() {
+            return Ok(LibraryIndex::default());
+        }
+
+        let file = OpenOptions::new().read(true).open(path)?;
+        let reader = BufReader::new(file);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn save(&self) -> Result<(), ParrotError> {
+        create_dir_all(SETTINGS_PATH.as_str())?;
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(Self::path())?;
+
+        let writer = BufWriter::new(file);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Walks the library for audio files, only reading the tags of files that changed since `previous`.
+    pub fn scan(root: &Path, previous: &LibraryIndex) -> LibraryIndex {
+        let known: HashMap<&str, &LibraryTrack> = previous
+            .tracks
+            .iter()
+            .map(|track| (track.path.as_str(), track))
+            .collect();
+
+        let mut tracks = Vec::new();
+        let mut dirs = vec![root.to_path_buf()];
+
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+
+                // symlinks aren't followed, so the walk can't go around in circles
+                match entry.file_type() {
+                    Ok(file_type) if file_type.is_dir() => {
+                        dirs.push(path);
+                        continue;
+                    }
+                    Ok(file_type) if file_type.is_file() && is_audio_file(&path) => {}
+                    _ => continue,
+                }
+
+                let Some(relative_path) = get_relative_path(root, &path) else {
+                    continue;
+                };
+
+                let modified = entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |modified| modified.as_secs());
+
+                if let Some(track) = known.get(relative_path.as_str()) {
+                    if track.modified == modified {
+                        tracks.push((*track).clone());
+                        continue;
+                    }
+                }
+
+                let Ok(file) = File::open(&path) else {
+                    continue;
+                };
+
+                if let Some(metadata) = probe_metadata(Box::new(file), &relative_path) {
+                    tracks.push(LibraryTrack {
+                        path: relative_path,
+                        modified,
+                        title: metadata.track,
+                        artist: metadata.artist,
+                        album: metadata.album,
+                        duration: metadata.duration,
+                    });
+                }
+            }
+        }
+
+        tracks.sort_by(|a, b| a.path.cmp(&b.path));
+        LibraryIndex { tracks }
+    }
+
+    /// Finds the track behind a library link, or the one matching the search best.
+    pub fn search(&self, query: &str) -> Option<&LibraryTrack> {
+        if let Some(path) = query.strip_prefix(LIBRARY_SCHEME) {
+            return self.tracks.iter().find(|track| track.path == path);
+        }
+
+        self.tracks
+            .iter()
+            .filter_map(|track| match_score(query, track).map(|score| (score, track)))
+            .fold(
+                None,
+                |best: Option<(u32, &LibraryTrack)>, (score, track)| match best {
+                    Some((best_score, _)) if best_score >= score => best,
+                    _ => Some((score, track)),
+                },
+            )
+            .map(|(_, track)| track)
+    }
+
+    pub fn stats(&self) -> LibraryStats {
+        let distinct = |field: fn(&LibraryTrack) -> &Option<String>| {
+            self.tracks
+                .iter()
+                .filter_map(|track| field(track).as_ref().map(|value| value.to_lowercase()))
+                .collect::<HashSet<String>>()
+                .len()
+        };
+
+        LibraryStats {
+            tracks: self.tracks.len(),
+            artists: distinct(|track| &track.artist),
+            albums: distinct(|track| &track.album),
+            duration: self.tracks.iter().filter_map(|track| track.duration).sum(),
+        }
+    }
+
+    fn path() -> String {
+        format!("{}/library.json", SETTINGS_PATH.as_str())
+    }
+}
+
+pub struct Library {}
+
+impl Library {
+    pub fn is_library_url(url: &str) -> bool {
+        url.starts_with(LIBRARY_SCHEME)
+    }
+
+    /// Where a library link's file is on disk, as long as it stays inside the library.
+    pub fn resolve(url: &str) -> Option<PathBuf> {
+        let root = LIBRARY_PATH.as_ref()?;
+        let path = Path::new(url.strip_prefix(LIBRARY_SCHEME)?);
+
+        let is_contained = path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+        is_contained.then(|| root.join(path))
+    }
+
+    /// Loads the index saved by the last scan, scanning in the background if there's none yet.
+    pub async fn load() -> Result<(), ParrotError> {
+        if LIBRARY_PATH.is_none() {
+            return Ok(());
+        }
+
+        let index = LibraryIndex::load()?;
+        let is_empty = index.tracks.is_empty();
+        *LIBRARY.write().await = index;
+
+        if is_empty {
+            tokio::spawn(async {
+                if let Err(err) = Library::rescan().await {
+                    println!("Failed to scan the library: {}", err);
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Rescans the library and saves the new index, returning how many tracks it holds.
+    pub async fn rescan() -> Result<usize, ParrotError> {
+        let root = verify(
+            LIBRARY_PATH.clone(),
+            ParrotError::Other(FAIL_LIBRARY_DISABLED),
+        )?;
+
+        let already_scanning = SCANNING.swap(true, Ordering::SeqCst);
+        verify(!already_scanning, ParrotError::Other(FAIL_LIBRARY_SCANNING))?;
+
+        let previous = LIBRARY.read().await.clone();
+        let index = tokio::task::spawn_blocking(move || LibraryIndex::scan(&root, &previous)).await;
+        SCANNING.store(false, Ordering::SeqCst);
+
+        let index = index.map_err(|_| ParrotError::Other("failed to scan the library"))?;
+        index.save()?;
+
+        let count = index.tracks.len();
+        *LIBRARY.write().await = index;
+        Ok(count)
+    }
+
+    pub async fn fetch_metadata(query: &str) -> Result<AuxMetadata, ParrotError> {
+        verify(
+            LIBRARY_PATH.is_some(),
+            ParrotError::Other(FAIL_LIBRARY_DISABLED),
+        )?;
+
+        let library = LIBRARY.read().await;
+        let track = verify(
+            library.search(query),
+            ParrotError::Other(FAIL_LIBRARY_NO_MATCH),
+        )?;
+
+        Ok(track.metadata())
+    }
+}
+
+/// Scores how well a track matches a search, or `None` if any of its words can't be found.
+/// Words found in the title count for more than those in the artist or album.
+pub fn match_score(query: &str, track: &LibraryTrack) -> Option<u32> {
+    let title = split_words(&track.display_title());
+    let details = split_words(&format!(
+        "{} {}",
+        track.artist.as_deref().unwrap_or_default(),
+        track.album.as_deref().unwrap_or_default()
+    ));
+
+    let mut score = 0;
+
+    for word in split_words(query) {
+        let title_score = word_score(&word, &title);
+        let details_score = word_score(&word, &details);
+
+        score += match (title_score, details_score) {
+            (0, 0) => return None,
+            (0, details_score) => details_score,
+            (title_score, _) => title_score + 1,
+        };
+    }
+
+    (score > 0).then_some(score)
+}
+
+/// How closely a word of the search matches the closest of the given words.
+fn word_score(word: &str, words: &[String]) -> u32 {
+    words
+        .iter()
+        .map(|candidate| {
+            if candidate == word {
+                3
+            } else if candidate.starts_with(word) {
+                2
+            } else if candidate.contains(word) || is_typo(word, candidate) {
+                1
+            } else {
+                0
+            }
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+/// Whether two longer words are a single insertion, removal or substitution apart.
+fn is_typo(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.len().min(b.len()) < 4 || a.len().abs_diff(b.len()) > 1 {
+        return false;
+    }
+
+    let prefix = a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count();
+    let (a_rest, b_rest) = (&a[prefix..], &b[prefix..]);
+
+    match a_rest.len().cmp(&b_rest.len()) {
+        std::cmp::Ordering::Equal => a_rest.get(1..) == b_rest.get(1..),
+        std::cmp::Ordering::Less => a_rest == &b_rest[1..],
+        std::cmp::Ordering::Greater => &a_rest[1..] == b_rest,
+    }
+}
+
+fn split_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| {
+            AUDIO_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+        })
+}
+
+/// The file's path inside the library, always separated by slashes.
+fn get_relative_path(root: &Path, path: &Path) -> Option<String> {
+    let components: Option<Vec<&str>> = path
+        .strip_prefix(root)
+        .ok()?
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect();
+
+    Some(components?.join("/"))
+}
@@ -1,3 +1,8 @@
-// pub mod ffmpeg;
+pub mod attachment;
+pub mod chapters;
+pub mod ffmpeg;
+pub mod library;
+pub mod radio;
+pub mod sponsorblock;
 pub mod spotify;
 // pub mod youtube;
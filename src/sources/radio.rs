@@ -0,0 +1,459 @@
+use reqwest::{
+    header::{HeaderMap, CONTENT_TYPE},
+    Client, Response,
+};
+use serenity::async_trait;
+use songbird::input::{
+    core::{io::MediaSource, probe::Hint},
+    AsyncAdapterStream, AsyncReadOnlySource, AudioStream, AudioStreamError, AuxMetadata, Compose,
+    HlsRequest, Input,
+};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use url::Url;
+
+use crate::{
+    errors::{verify, ParrotError},
+    messaging::messages::{
+        FAIL_RADIO_EMPTY, FAIL_RADIO_INVALID_PLAYLIST, FAIL_RADIO_NOT_AUDIO, FAIL_RADIO_UNREACHABLE,
+    },
+};
+
+const PLAYLIST_EXTENSIONS: [&str; 3] = ["m3u", "m3u8", "pls"];
+
+// radio playlists only list a handful of streams, anything bigger is something else
+const MAX_PLAYLIST_SIZE: usize = 64 * 1024;
+const STREAM_BUFFER_SIZE: usize = 64 * 1024;
+
+/// A stream listed in a radio playlist, along with the station name it was given there.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaylistEntry {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+/// Where a radio playlist's audio comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RadioPlaylist {
+    /// Icecast or Shoutcast streams, tried in order until one answers.
+    Streams(Vec<PlaylistEntry>),
+    /// HLS playlists are the stream themselves.
+    Hls,
+}
+
+/// A stream that was connected to, ready to be read from.
+pub struct RadioConnection {
+    response: Response,
+    title: Option<String>,
+}
+
+impl RadioConnection {
+    /// What the station says its audio is encoded as.
+    pub fn hint(&self) -> Option<Hint> {
+        get_header(self.response.headers(), CONTENT_TYPE.as_str()).map(|mime| {
+            let mut hint = Hint::new();
+            hint.mime_type(&mime);
+            hint
+        })
+    }
+
+    /// Copies the station's audio into `writer` in the background, until the reading end
+    /// goes away, keeping the latest song title sent along in `stream_title`.
+    pub fn forward<W>(self, mut writer: W, stream_title: Arc<Mutex<Option<String>>>)
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let mut response = self.response;
+
+        // servers that ignored the request send plain audio
+        let mut demuxer = get_header(response.headers(), "icy-metaint")
+            .and_then(|metaint| metaint.parse().ok())
+            .filter(|metaint| *metaint > 0)
+            .map(IcyDemuxer::new);
+
+        tokio::spawn(async move {
+            let mut audio = Vec::new();
+
+            while let Ok(Some(chunk)) = response.chunk().await {
+                let chunk = match &mut demuxer {
+                    Some(demuxer) => {
+                        audio.clear();
+                        if let Some(title) = demuxer.demux(&chunk, &mut audio) {
+                            *stream_title.lock().unwrap() = Some(title);
+                        }
+                        &audio[..]
+                    }
+                    None => &chunk[..],
+                };
+
+                if writer.write_all(chunk).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+pub struct RadioSource {}
+
+impl RadioSource {
+    /// Whether the link points at a PLS or M3U playlist rather than a page yt-dlp can scrape.
+    pub fn is_playlist_url(url: &str) -> bool {
+        Url::parse(url)
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .and_then(|url| {
+                let extension = Path::new(url.path()).extension()?.to_str()?;
+                Some(PLAYLIST_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Connects to the station to read what it calls itself from its ICY headers.
+    pub async fn fetch_metadata(url: &str) -> Result<AuxMetadata, ParrotError> {
+        let client = Client::new();
+
+        let title = match Self::connect(&client, url).await? {
+            Some(RadioConnection { response, title }) => get_header(response.headers(), "icy-name")
+                .or(title)
+                .or_else(|| get_host(response.url().as_str())),
+            None => get_host(url),
+        };
+
+        Ok(AuxMetadata {
+            title: title.clone(),
+            channel: title,
+            source_url: Some(url.to_string()),
+            ..Default::default()
+        })
+    }
+
+    async fn fetch_playlist(client: &Client, url: &str) -> Result<RadioPlaylist, ParrotError> {
+        let mut response = client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|_| ParrotError::Other(FAIL_RADIO_UNREACHABLE))?;
+
+        // read in chunks, since a link that only looks like a playlist could be an endless stream
+        let mut contents = Vec::new();
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|_| ParrotError::Other(FAIL_RADIO_UNREACHABLE))?
+        {
+            contents.extend_from_slice(&chunk);
+            verify(
+                contents.len() <= MAX_PLAYLIST_SIZE,
+                ParrotError::Other(FAIL_RADIO_INVALID_PLAYLIST),
+            )?;
+        }
+
+        let contents = String::from_utf8_lossy(&contents);
+
+        // streams may be listed relative to the playlist
+        let base = Url::parse(url).ok();
+
+        Ok(match parse_playlist(&contents) {
+            RadioPlaylist::Streams(entries) => RadioPlaylist::Streams(
+                entries
+                    .into_iter()
+                    .filter_map(|entry| {
+                        let url = match &base {
+                            Some(base) => base.join(&entry.url).ok()?,
+                            None => Url::parse(&entry.url).ok()?,
+                        };
+
+                        Some(PlaylistEntry {
+                            url: url.to_string(),
+                            title: entry.title,
+                        })
+                    })
+                    .collect(),
+            ),
+            RadioPlaylist::Hls => RadioPlaylist::Hls,
+        })
+    }
+
+    /// Connects to the first of the playlist's streams that answers, or `None` for HLS playlists.
+    pub async fn connect(
+        client: &Client,
+        url: &str,
+    ) -> Result<Option<RadioConnection>, ParrotError> {
+        let entries = match Self::fetch_playlist(client, url).await? {
+            RadioPlaylist::Streams(entries) => entries,
+            RadioPlaylist::Hls => return Ok(None),
+        };
+        verify(!entries.is_empty(), ParrotError::Other(FAIL_RADIO_EMPTY))?;
+
+        let mut found_text = false;
+
+        for entry in entries {
+            // asks the server to interleave the song titles with the audio
+            let response = client
+                .get(&entry.url)
+                .header("Icy-MetaData", "1")
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+
+            let Ok(response) = response else {
+                continue;
+            };
+
+            // playlists sometimes link to the station's website next to its streams
+            let is_text = get_header(response.headers(), CONTENT_TYPE.as_str())
+                .map_or(false, |mime| mime.starts_with("text/"));
+            if is_text {
+                found_text = true;
+                continue;
+            }
+
+            return Ok(Some(RadioConnection {
+                response,
+                title: entry.title,
+            }));
+        }
+
+        if found_text {
+            Err(ParrotError::Other(FAIL_RADIO_NOT_AUDIO))
+        } else {
+            Err(ParrotError::Other(FAIL_RADIO_UNREACHABLE))
+        }
+    }
+}
+
+/// A lazily connected radio stream, which passes on the song titles the station sends along.
+#[derive(Clone, Debug)]
+pub struct RadioStream {
+    url: String,
+    stream_title: Arc<Mutex<Option<String>>>,
+}
+
+impl RadioStream {
+    pub fn new(url: String, stream_title: Arc<Mutex<Option<String>>>) -> Self {
+        Self { url, stream_title }
+    }
+}
+
+impl From<RadioStream> for Input {
+    fn from(val: RadioStream) -> Self {
+        Input::Lazy(Box::new(val))
+    }
+}
+
+#[async_trait]
+impl Compose for RadioStream {
+    fn create(&mut self) -> Result<AudioStream<Box<dyn MediaSource>>, AudioStreamError> {
+        Err(AudioStreamError::Unsupported)
+    }
+
+    async fn create_async(
+        &mut self,
+    ) -> Result<AudioStream<Box<dyn MediaSource>>, AudioStreamError> {
+        let client = Client::new();
+
+        let connection = RadioSource::connect(&client, &self.url)
+            .await
+            .map_err(|err| AudioStreamError::Fail(err.to_string().into()))?;
+
+        let Some(connection) = connection else {
+            return HlsRequest::new(client, self.url.clone())
+                .create_async()
+                .await;
+        };
+
+        let hint = connection.hint();
+
+        // runs until the track drops its end of the pipe
+        let (writer, reader) = tokio::io::duplex(STREAM_BUFFER_SIZE);
+        connection.forward(writer, self.stream_title.clone());
+
+        let stream = AsyncAdapterStream::new(
+            Box::new(AsyncReadOnlySource::new(reader)),
+            STREAM_BUFFER_SIZE,
+        );
+
+        Ok(AudioStream {
+            input: Box::new(stream),
+            hint,
+        })
+    }
+
+    fn should_create_async(&self) -> bool {
+        true
+    }
+}
+
+/// Splits the ICY metadata blocks a station sends every `metaint` bytes out of its audio.
+pub struct IcyDemuxer {
+    metaint: usize,
+    state: IcyState,
+    metadata: Vec<u8>,
+}
+
+enum IcyState {
+    Audio(usize),
+    Length,
+    Metadata(usize),
+}
+
+impl IcyDemuxer {
+    pub fn new(metaint: usize) -> Self {
+        Self {
+            metaint,
+            state: IcyState::Audio(metaint),
+            metadata: Vec::new(),
+        }
+    }
+
+    /// Appends the audio in `chunk` to `audio`, returning the last song title it completed.
+    pub fn demux(&mut self, mut chunk: &[u8], audio: &mut Vec<u8>) -> Option<String> {
+        let mut title = None;
+
+        while !chunk.is_empty() {
+            match self.state {
+                IcyState::Audio(left) => {
+                    let len = left.min(chunk.len());
+                    audio.extend_from_slice(&chunk[..len]);
+                    chunk = &chunk[len..];
+
+                    self.state = match left - len {
+                        0 => IcyState::Length,
+                        left => IcyState::Audio(left),
+                    };
+                }
+                IcyState::Length => {
+                    // the length is given in blocks of 16 bytes
+                    self.state = match chunk[0] as usize * 16 {
+                        0 => IcyState::Audio(self.metaint),
+                        len => IcyState::Metadata(len),
+                    };
+                    self.metadata.clear();
+                    chunk = &chunk[1..];
+                }
+                IcyState::Metadata(left) => {
+                    let len = left.min(chunk.len());
+                    self.metadata.extend_from_slice(&chunk[..len]);
+                    chunk = &chunk[len..];
+
+                    self.state = match left - len {
+                        0 => {
+                            let metadata = String::from_utf8_lossy(&self.metadata);
+                            title = parse_stream_title(&metadata).or(title);
+                            IcyState::Audio(self.metaint)
+                        }
+                        left => IcyState::Metadata(left),
+                    };
+                }
+            }
+        }
+
+        title
+    }
+}
+
+/// Reads the song title out of an ICY metadata block, e.g. `StreamTitle='Artist - Song';`.
+pub fn parse_stream_title(metadata: &str) -> Option<String> {
+    let start = metadata.find("StreamTitle='")? + "StreamTitle='".len();
+    let rest = &metadata[start..];
+
+    // titles may contain quotes themselves, so only a quote ending the field counts
+    let end = rest
+        .find("';")
+        .unwrap_or_else(|| rest.trim_end_matches('\0').len());
+    let title = rest[..end].trim_end_matches('\'').trim();
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Reads the streams listed in a PLS or M3U playlist, in the order they should be tried.
+pub fn parse_playlist(contents: &str) -> RadioPlaylist {
+    // HLS media and master playlists are both marked by their own tags
+    if contents.contains("#EXT-X-") {
+        return RadioPlaylist::Hls;
+    }
+
+    let lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+
+    let is_pls = contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map_or(false, |line| line.eq_ignore_ascii_case("[playlist]"));
+
+    if is_pls {
+        let mut entries: Vec<(String, PlaylistEntry)> = Vec::new();
+        let mut titles: Vec<(String, String)> = Vec::new();
+
+        for line in lines {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim().to_string();
+
+            if let Some(index) = key.strip_prefix("file") {
+                entries.push((
+                    index.to_string(),
+                    PlaylistEntry {
+                        url: value,
+                        title: None,
+                    },
+                ));
+            } else if let Some(index) = key.strip_prefix("title") {
+                titles.push((index.to_string(), value));
+            }
+        }
+
+        for (index, title) in titles {
+            if let Some((_, entry)) = entries.iter_mut().find(|(i, _)| *i == index) {
+                entry.title = Some(title).filter(|title| !title.is_empty());
+            }
+        }
+
+        return RadioPlaylist::Streams(entries.into_iter().map(|(_, entry)| entry).collect());
+    }
+
+    let mut entries = Vec::new();
+    let mut title = None;
+
+    for line in lines {
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            // `#EXTINF:<duration>,<title>`
+            title = info
+                .split_once(',')
+                .map(|(_, title)| title.trim().to_string())
+                .filter(|title| !title.is_empty());
+        } else if !line.starts_with('#') {
+            entries.push(PlaylistEntry {
+                url: line.to_string(),
+                title: title.take(),
+            });
+        }
+    }
+
+    RadioPlaylist::Streams(entries)
+}
+
+fn get_header(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn get_host(url: &str) -> Option<String> {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.replace("www.", "")))
+}
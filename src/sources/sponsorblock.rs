@@ -0,0 +1,72 @@
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::{env, time::Duration};
+
+use crate::errors::ParrotError;
+
+const DEFAULT_SPONSORBLOCK_API_URL: &str = "https://sponsor.ajay.app";
+
+// segments which rarely belong in a music queue
+const SKIP_CATEGORIES: &str =
+    r#"["sponsor","selfpromo","interaction","intro","outro","music_offtopic"]"#;
+
+lazy_static! {
+    pub static ref SPONSORBLOCK_API_URL: String =
+        env::var("SPONSORBLOCK_API_URL").unwrap_or(DEFAULT_SPONSORBLOCK_API_URL.to_string());
+}
+
+/// A stretch of a video, in source time, that should be skipped over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkipSegment {
+    pub start: Duration,
+    pub end: Duration,
+}
+
+#[derive(Deserialize)]
+struct SponsorBlockSegment {
+    segment: (f64, f64),
+}
+
+pub struct SponsorBlock {}
+
+impl SponsorBlock {
+    /// Fetches the skip segments submitted for the given YouTube video ID.
+    pub async fn fetch_segments(
+        base_url: &str,
+        video_id: &str,
+    ) -> Result<Vec<SkipSegment>, ParrotError> {
+        let url = format!("{}/api/skipSegments", base_url.trim_end_matches('/'));
+
+        let response = reqwest::Client::new()
+            .get(url)
+            .query(&[("videoID", video_id), ("categories", SKIP_CATEGORIES)])
+            .send()
+            .await
+            .map_err(|_| ParrotError::Other("failed to reach the SponsorBlock API"))?;
+
+        // videos nobody submitted segments for are reported as not found
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+
+        let body = response
+            .error_for_status()
+            .map_err(|_| ParrotError::Other("the SponsorBlock API returned an error"))?
+            .text()
+            .await
+            .map_err(|_| ParrotError::Other("failed to read the SponsorBlock segments"))?;
+
+        let segments: Vec<SponsorBlockSegment> = serde_json::from_str(&body)?;
+
+        let segments = segments
+            .into_iter()
+            .filter(|s| s.segment.0 >= 0.0 && s.segment.1 > s.segment.0)
+            .map(|s| SkipSegment {
+                start: Duration::from_secs_f64(s.segment.0),
+                end: Duration::from_secs_f64(s.segment.1),
+            })
+            .collect();
+
+        Ok(segments)
+    }
+}
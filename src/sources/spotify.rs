@@ -7,12 +7,18 @@ use lazy_static::lazy_static;
 use regex::Regex;
 use rspotify::{
     clients::BaseClient,
-    model::{AlbumId, PlayableItem, PlaylistId, SimplifiedArtist, TrackId},
+    model::{
+        AlbumId, ArtistId, PlayableItem, PlaylistId, RecommendationsAttribute, SearchResult,
+        SearchType, SimplifiedArtist, TrackId,
+    },
     ClientCredsSpotify, Credentials,
 };
 use std::{env, str::FromStr};
 use tokio::sync::Mutex;
 
+// how many recommended tracks are considered for autoplay
+const RECOMMENDATIONS_SIZE: u32 = 10;
+
 lazy_static! {
     pub static ref SPOTIFY: Mutex<Result<ClientCredsSpotify, ParrotError>> =
         Mutex::new(Err(ParrotError::Other("no auth attempts")));
@@ -156,6 +162,47 @@ impl Spotify {
         Ok(QueryType::KeywordList(query_list))
     }
 
+    /// Looks up the track best matching `query` and returns search queries for the tracks
+    /// Spotify recommends alongside it.
+    pub async fn get_recommendations(
+        spotify: &ClientCredsSpotify,
+        query: &str,
+    ) -> Result<Vec<String>, ParrotError> {
+        let result = spotify
+            .search(query, SearchType::Track, None, None, Some(1), None)
+            .await
+            .map_err(|_| ParrotError::Other("failed to search tracks"))?;
+
+        let seed_id = match result {
+            SearchResult::Tracks(page) => page.items.into_iter().next().and_then(|track| track.id),
+            _ => None,
+        }
+        .ok_or(ParrotError::Other("failed to find a seed track"))?;
+
+        let recommendations = spotify
+            .recommendations(
+                std::iter::empty::<RecommendationsAttribute>(),
+                None::<Vec<ArtistId>>,
+                None::<Vec<&str>>,
+                Some([seed_id]),
+                None,
+                Some(RECOMMENDATIONS_SIZE),
+            )
+            .await
+            .map_err(|_| ParrotError::Other("failed to fetch recommendations"))?;
+
+        let query_list = recommendations
+            .tracks
+            .iter()
+            .map(|track| {
+                let artist_names = Self::join_artist_names(&track.artists);
+                Self::build_query(&artist_names, &track.name)
+            })
+            .collect();
+
+        Ok(query_list)
+    }
+
     fn build_query(artists: &str, track_name: &str) -> String {
         format!("{} - {}", artists, track_name)
     }
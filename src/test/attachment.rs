@@ -0,0 +1,59 @@
+use std::{io::Cursor, time::Duration};
+
+use crate::sources::attachment::{probe_metadata, AttachmentSource};
+
+/// Builds a silent 16-bit mono WAV file with an INFO chunk naming the track.
+pub fn build_wav(sample_rate: u32, seconds: u32, title: &str) -> Vec<u8> {
+    let data_len = sample_rate * seconds * 2;
+
+    let mut title = title.as_bytes().to_vec();
+    title.push(0);
+    if title.len() % 2 == 1 {
+        title.push(0);
+    }
+
+    let mut info = b"INFOINAM".to_vec();
+    info.extend((title.len() as u32).to_le_bytes());
+    info.extend(title);
+
+    let mut wav = b"RIFF".to_vec();
+    wav.extend((4 + 24 + 8 + info.len() as u32 + 8 + data_len).to_le_bytes());
+    wav.extend(b"WAVEfmt ");
+    wav.extend(16u32.to_le_bytes());
+    wav.extend(1u16.to_le_bytes()); // PCM
+    wav.extend(1u16.to_le_bytes()); // mono
+    wav.extend(sample_rate.to_le_bytes());
+    wav.extend((sample_rate * 2).to_le_bytes());
+    wav.extend(2u16.to_le_bytes());
+    wav.extend(16u16.to_le_bytes());
+    wav.extend(b"LIST");
+    wav.extend((info.len() as u32).to_le_bytes());
+    wav.extend(info);
+    wav.extend(b"data");
+    wav.extend(data_len.to_le_bytes());
+    wav.extend(vec![0; data_len as usize]);
+    wav
+}
+
+#[test]
+fn test_probe_metadata() {
+    let wav = build_wav(8000, 3, "Silence");
+
+    let metadata = probe_metadata(Box::new(Cursor::new(wav)), "recording.wav").unwrap();
+    assert_eq!(metadata.title.as_deref(), Some("Silence"));
+    assert_eq!(metadata.duration, Some(Duration::from_secs(3)));
+    assert_eq!(metadata.sample_rate, Some(8000));
+    assert_eq!(metadata.channels, Some(1));
+
+    assert!(probe_metadata(Box::new(Cursor::new(b"not audio at all")), "notes.txt").is_none());
+}
+
+#[test]
+fn test_is_attachment_url() {
+    assert!(AttachmentSource::is_attachment_url(
+        "https://cdn.discordapp.com/attachments/1/2/song.mp3"
+    ));
+    assert!(!AttachmentSource::is_attachment_url(
+        "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+    ));
+}
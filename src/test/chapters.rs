@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use crate::sources::chapters::{parse_chapters, select_chapter, Chapter};
+
+fn chapter(title: &str, start: u64, end: u64) -> Chapter {
+    Chapter {
+        title: title.to_string(),
+        start: Duration::from_secs(start),
+        end: Duration::from_secs(end),
+    }
+}
+
+#[test]
+fn test_parse_chapters() {
+    let json = r#"{"title":"Album","chapters":[
+        {"title":"Intro","start_time":0.0,"end_time":60.0},
+        {"title":"Song","start_time":60.0,"end_time":240.0}
+    ]}"#;
+    assert_eq!(
+        parse_chapters(json).unwrap(),
+        vec![chapter("Intro", 0, 60), chapter("Song", 60, 240)]
+    );
+
+    let json = r#"{"title":"Single","chapters":null}"#;
+    assert_eq!(parse_chapters(json).unwrap(), vec![]);
+}
+
+#[test]
+fn test_select_chapter() {
+    let chapters = vec![
+        chapter("Intro", 0, 60),
+        chapter("Midnight City", 60, 240),
+        chapter("Outro", 240, 300),
+    ];
+    let position = Duration::from_secs(90);
+
+    assert_eq!(select_chapter(&chapters, position, "next"), Some(2));
+    assert_eq!(select_chapter(&chapters, position, "previous"), Some(0));
+    assert_eq!(select_chapter(&chapters, position, "outro"), Some(2));
+    assert_eq!(select_chapter(&chapters, position, "midnight"), Some(1));
+    assert_eq!(select_chapter(&chapters, position, "bridge"), None);
+
+    let position = Duration::from_secs(250);
+    assert_eq!(select_chapter(&chapters, position, "next"), None);
+}
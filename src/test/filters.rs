@@ -0,0 +1,101 @@
+use std::str::FromStr;
+
+use crate::filters::{
+    build_filter_graph, calculate_tempo, AudioFilter, AudioFilters, PlaybackRate,
+};
+
+#[test]
+fn test_audio_filter_from_str() {
+    for filter in AudioFilter::ALL {
+        assert_eq!(AudioFilter::from_str(filter.name()), Ok(filter));
+    }
+
+    assert_eq!(AudioFilter::from_str("off"), Err(()));
+}
+
+#[test]
+fn test_build_filter_graph() {
+    let filters = AudioFilters::new();
+    assert_eq!(
+        build_filter_graph(&filters, &PlaybackRate::default(), false),
+        None
+    );
+
+    let filters = AudioFilters::from([AudioFilter::EightD, AudioFilter::BassBoost]);
+    assert_eq!(
+        build_filter_graph(&filters, &PlaybackRate::default(), false),
+        Some("bass=g=10:f=110:w=0.6,apulsator=hz=0.125".to_string())
+    );
+}
+
+#[test]
+fn test_build_filter_graph_normalize() {
+    let filters = AudioFilters::new();
+    assert_eq!(
+        build_filter_graph(&filters, &PlaybackRate::default(), true),
+        Some("loudnorm=I=-14:TP=-1.5:LRA=11".to_string())
+    );
+
+    let filters = AudioFilters::from([AudioFilter::Karaoke]);
+    assert_eq!(
+        build_filter_graph(&filters, &PlaybackRate::default(), true),
+        Some("pan=stereo|c0=c0-c1|c1=c1-c0,loudnorm=I=-14:TP=-1.5:LRA=11".to_string())
+    );
+}
+
+#[test]
+fn test_calculate_tempo() {
+    let filters = AudioFilters::from([AudioFilter::Karaoke]);
+    assert_eq!(calculate_tempo(&filters, &PlaybackRate::default()), 1.0);
+
+    let filters = AudioFilters::from([AudioFilter::Nightcore, AudioFilter::Vaporwave]);
+    assert_eq!(calculate_tempo(&filters, &PlaybackRate::default()), 1.0);
+
+    let filters = AudioFilters::from([AudioFilter::Nightcore]);
+    assert_eq!(calculate_tempo(&filters, &PlaybackRate::default()), 1.25);
+}
+
+#[test]
+fn test_playback_rate_filters() {
+    let rate = PlaybackRate::default();
+    assert!(rate.ffmpeg_filters().is_empty());
+
+    let rate = PlaybackRate {
+        speed: 1.5,
+        pitch: 1.0,
+    };
+    assert_eq!(rate.ffmpeg_filters(), vec!["atempo=1.5"]);
+
+    let rate = PlaybackRate {
+        speed: 2.0,
+        pitch: 2.0,
+    };
+    assert_eq!(
+        rate.ffmpeg_filters(),
+        vec!["aresample=48000,asetrate=96000,aresample=48000"]
+    );
+
+    // shifting the pitch down while speeding up exceeds a single atempo's range
+    let rate = PlaybackRate {
+        speed: 2.0,
+        pitch: 0.5,
+    };
+    assert_eq!(
+        rate.ffmpeg_filters(),
+        vec![
+            "aresample=48000,asetrate=24000,aresample=48000",
+            "atempo=2",
+            "atempo=2"
+        ]
+    );
+}
+
+#[test]
+fn test_calculate_tempo_with_speed() {
+    let filters = AudioFilters::from([AudioFilter::Nightcore]);
+    let rate = PlaybackRate {
+        speed: 2.0,
+        pitch: 0.5,
+    };
+    assert_eq!(calculate_tempo(&filters, &rate), 2.5);
+}
@@ -0,0 +1,68 @@
+use std::{env, fs, process};
+
+use crate::{
+    sources::library::{match_score, LibraryIndex, LibraryTrack},
+    test::attachment::build_wav,
+};
+
+fn track(path: &str, title: &str, artist: &str, album: &str) -> LibraryTrack {
+    LibraryTrack {
+        path: path.to_string(),
+        title: Some(title.to_string()),
+        artist: Some(artist.to_string()),
+        album: Some(album.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_library_search() {
+    let index = LibraryIndex {
+        tracks: vec![
+            track("a.mp3", "Northern Lights", "Aurora Band", "Skies"),
+            track("b.mp3", "Aurora", "Northern Choir", "Lights Out"),
+            track("c.mp3", "Harbour", "The Tides", "Coastline"),
+        ],
+    };
+
+    // title matches outrank the same words in the artist or album
+    let result = index.search("aurora").unwrap();
+    assert_eq!(result.path, "b.mp3");
+
+    let result = index.search("northern lights").unwrap();
+    assert_eq!(result.path, "a.mp3");
+
+    // prefixes and small typos still find the track
+    assert_eq!(index.search("harb").unwrap().path, "c.mp3");
+    assert!(index.search("coastlnie tides").is_none());
+    assert_eq!(index.search("harbur").unwrap().path, "c.mp3");
+
+    assert!(index.search("symphony").is_none());
+    assert_eq!(index.search("library:c.mp3").unwrap().path, "c.mp3");
+    assert!(index.search("library:missing.mp3").is_none());
+
+    assert!(match_score("tides coastline", &index.tracks[2]).is_some());
+    assert!(match_score("tides ocean", &index.tracks[2]).is_none());
+}
+
+#[test]
+fn test_library_scan() {
+    let root = env::temp_dir().join(format!("parrot-library-{}", process::id()));
+    fs::create_dir_all(root.join("Album")).unwrap();
+    fs::write(root.join("Album/first.wav"), build_wav(8000, 2, "First")).unwrap();
+    fs::write(root.join("second.wav"), build_wav(8000, 1, "Second")).unwrap();
+    fs::write(root.join("cover.txt"), "not audio").unwrap();
+
+    let index = LibraryIndex::scan(&root, &LibraryIndex::default());
+    let paths: Vec<&str> = index.tracks.iter().map(|t| t.path.as_str()).collect();
+    assert_eq!(paths, vec!["Album/first.wav", "second.wav"]);
+    assert_eq!(index.tracks[0].title.as_deref(), Some("First"));
+
+    // files that haven't changed since the last scan aren't read again
+    let mut previous = index.clone();
+    previous.tracks[1].title = Some(String::from("Cached"));
+    let index = LibraryIndex::scan(&root, &previous);
+    assert_eq!(index.tracks[1].title.as_deref(), Some("Cached"));
+
+    fs::remove_dir_all(root).unwrap();
+}
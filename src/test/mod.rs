@@ -1,2 +1,9 @@
+pub mod attachment;
+pub mod chapters;
 pub mod errors;
+pub mod filters;
+pub mod library;
+pub mod queue;
+pub mod radio;
+pub mod sponsorblock;
 pub mod utils;
@@ -0,0 +1,260 @@
+use crate::{
+    commands::{
+        play::{fair_slot, limit_playlist, rotate_upcoming, PlaylistSummary, QueryType},
+        queue::{count_pages, turn_page},
+        queue_file::{format_queue_file, parse_queue_file, QueueFileFormat},
+    },
+    errors::ParrotError,
+    guild::{
+        persisted_queue::{PersistedQueue, PersistedTrack},
+        settings::{DuplicatePolicy, GuildSettings, GuildSettingsMap, QueueLimit},
+    },
+    utils::PlaybackRange,
+};
+use serenity::{
+    model::id::{ChannelId, GuildId, UserId},
+    prelude::{RwLock, TypeMap},
+};
+use songbird::input::AuxMetadata;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+#[test]
+fn test_rotate_upcoming() {
+    // jumping to the third upcoming track sends the two before it to the back
+    let mut queue = vec![0, 1, 2, 3, 4];
+    rotate_upcoming(&mut queue, 2);
+    assert_eq!(queue, vec![0, 3, 4, 1, 2]);
+
+    let mut queue = vec![0, 1, 2];
+    rotate_upcoming(&mut queue, 2);
+    assert_eq!(queue, vec![0, 1, 2]);
+
+    let mut queue = vec![0];
+    rotate_upcoming(&mut queue, 1);
+    assert_eq!(queue, vec![0]);
+}
+
+#[test]
+fn test_fair_slot() {
+    fn enqueue(queue: &mut Vec<&'static str>, requester: &'static str) {
+        let slot = fair_slot(queue, &requester);
+        queue.insert(slot, requester);
+    }
+
+    // someone else's track doesn't wait behind a whole playlist
+    let mut queue = vec![];
+    for requester in ["a", "a", "a", "a", "b", "c", "b"] {
+        enqueue(&mut queue, requester);
+    }
+    assert_eq!(queue, vec!["a", "b", "c", "a", "b", "a", "a"]);
+
+    // the track being played counts as its requester's turn
+    let mut queue = vec!["a", "a"];
+    enqueue(&mut queue, "b");
+    assert_eq!(queue, vec!["a", "b", "a"]);
+
+    assert_eq!(fair_slot::<&str>(&[], &"a"), 0);
+}
+
+#[test]
+fn test_fair_slot_keeps_manual_order() {
+    // both of b's tracks were moved up by hand, which a later addition leaves alone
+    let mut queue = vec!["a", "b", "b", "a", "a"];
+    let slot = fair_slot(&queue, &"c");
+    queue.insert(slot, "c");
+    assert_eq!(queue, vec!["a", "b", "c", "b", "a", "a"]);
+
+    // a requester's new track still goes after their own tracks
+    let mut queue = vec!["a", "b", "b", "a", "a"];
+    let slot = fair_slot(&queue, &"b");
+    queue.insert(slot, "b");
+    assert_eq!(queue, vec!["a", "b", "b", "a", "a", "b"]);
+}
+
+#[test]
+fn test_duplicate_policy() {
+    let queued = vec![
+        "https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string(),
+        "https://soundcloud.com/artist/track".to_string(),
+        "https://www.youtube.com/watch?v=9bZkp7q19f0".to_string(),
+    ];
+
+    assert!(!DuplicatePolicy::Allow.rejects(&queued, "https://soundcloud.com/artist/track"));
+
+    // the same video behind a different link is still a duplicate
+    assert!(DuplicatePolicy::Reject.rejects(&queued, "https://youtu.be/dQw4w9WgXcQ"));
+    assert!(!DuplicatePolicy::Reject.rejects(&queued, "https://youtu.be/jNQXAC9IVRw"));
+
+    let policy = DuplicatePolicy::RejectWithin(2);
+    assert!(policy.rejects(&queued, "https://soundcloud.com/artist/track"));
+    assert!(!policy.rejects(&queued, "https://youtu.be/dQw4w9WgXcQ"));
+}
+
+#[test]
+fn test_queue_file_round_trip() {
+    let urls = [
+        "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+        "https://www.youtube.com/watch?v=9bZkp7q19f0",
+    ];
+    let tracks: Vec<PersistedTrack> = urls
+        .iter()
+        .map(|url| PersistedTrack {
+            source_url: url.to_string(),
+            metadata: AuxMetadata {
+                title: Some(String::from("Title")),
+                duration: Some(Duration::from_secs(212)),
+                ..Default::default()
+            },
+            requester: UserId::new(1),
+            range: Default::default(),
+        })
+        .collect();
+
+    for format in [
+        QueueFileFormat::M3u,
+        QueueFileFormat::Json,
+        QueueFileFormat::Txt,
+    ] {
+        let contents = format_queue_file(&tracks, format).unwrap();
+        assert_eq!(parse_queue_file(&contents), urls);
+    }
+
+    let contents = "#EXTM3U\n#EXTINF:-1,Radio\n\n  never gonna give you up  \n";
+    assert_eq!(parse_queue_file(contents), vec!["never gonna give you up"]);
+}
+
+#[test]
+fn test_persisted_queue_round_trip() {
+    let queue = PersistedQueue {
+        guild_id: GuildId::new(1),
+        voice_channel_id: ChannelId::new(2),
+        text_channel_id: ChannelId::new(3),
+        position: Duration::from_secs(42),
+        tracks: vec![
+            PersistedTrack {
+                source_url: String::from("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+                metadata: AuxMetadata {
+                    title: Some(String::from("Never Gonna Give You Up")),
+                    channel: Some(String::from("Rick Astley")),
+                    duration: Some(Duration::from_secs(212)),
+                    source_url: Some(String::from("https://www.youtube.com/watch?v=dQw4w9WgXcQ")),
+                    ..Default::default()
+                },
+                requester: UserId::new(4),
+                range: PlaybackRange::default(),
+            },
+            PersistedTrack {
+                source_url: String::from("https://cdn.discordapp.com/attachments/1/2/song.mp3"),
+                metadata: AuxMetadata::default(),
+                requester: UserId::new(5),
+                range: PlaybackRange {
+                    start: Some(Duration::from_secs(30)),
+                    end: None,
+                },
+            },
+        ],
+    };
+
+    let json = serde_json::to_string(&queue).unwrap();
+    let restored: PersistedQueue = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.guild_id, queue.guild_id);
+    assert_eq!(restored.voice_channel_id, queue.voice_channel_id);
+    assert_eq!(restored.text_channel_id, queue.text_channel_id);
+    assert_eq!(restored.position, queue.position);
+    assert_eq!(restored.tracks.len(), 2);
+    assert_eq!(
+        restored.tracks[0].metadata.title,
+        queue.tracks[0].metadata.title
+    );
+    assert_eq!(
+        restored.tracks[0].metadata.duration,
+        queue.tracks[0].metadata.duration
+    );
+    assert_eq!(restored.tracks[1].requester, UserId::new(5));
+    assert_eq!(restored.tracks[1].range, queue.tracks[1].range);
+
+    // restoring queues each track again the way it was first played
+    assert_eq!(
+        restored.stored_queue(),
+        vec![
+            (
+                QueryType::VideoLink(queue.tracks[0].source_url.clone()),
+                UserId::new(4)
+            ),
+            (
+                QueryType::File(queue.tracks[1].source_url.clone()),
+                UserId::new(5)
+            ),
+        ]
+    );
+
+    // queues saved before playback ranges existed still load
+    let json = r#"{"source_url":"https://youtu.be/dQw4w9WgXcQ","metadata":{"track":null,"artist":null,"album":null,"date":null,"channels":null,"channel":null,"start_time":null,"duration":null,"sample_rate":null,"source_url":null,"title":null,"thumbnail":null},"requester":"4"}"#;
+    let track: PersistedTrack = serde_json::from_str(json).unwrap();
+    assert_eq!(track.range, PlaybackRange::default());
+}
+
+#[test]
+fn test_pagination() {
+    assert_eq!(count_pages(0), 1);
+    assert_eq!(count_pages(6), 1);
+    assert_eq!(count_pages(7), 2);
+
+    assert_eq!(turn_page(">", 0, 3), Some(1));
+    assert_eq!(turn_page(">", 2, 3), Some(2));
+    assert_eq!(turn_page("<", 0, 3), Some(0));
+    assert_eq!(turn_page(">>", 0, 3), Some(2));
+    assert_eq!(turn_page("<<", 2, 3), Some(0));
+
+    // the list shrank while someone was on a later page
+    assert_eq!(turn_page("<", 4, 2), Some(1));
+    assert_eq!(turn_page("play", 0, 3), None);
+}
+
+#[tokio::test]
+async fn test_limit_playlist() {
+    let guild_id = GuildId::new(1);
+    let data = Arc::new(RwLock::new(TypeMap::new()));
+
+    // guilds without settings have no limit
+    let mut items = vec![1, 2, 3];
+    assert_eq!(limit_playlist(&mut items, &data, guild_id).await, None);
+    assert_eq!(items.len(), 3);
+
+    let mut settings = GuildSettings::new(guild_id);
+    settings.max_playlist_size = Some(2);
+    data.write()
+        .await
+        .insert::<GuildSettingsMap>(HashMap::from([(guild_id, settings)]));
+
+    assert_eq!(
+        limit_playlist(&mut items, &data, guild_id).await,
+        Some(QueueLimit::PlaylistSize(2))
+    );
+    assert_eq!(items, vec![1, 2]);
+
+    assert_eq!(limit_playlist(&mut items, &data, guild_id).await, None);
+}
+
+#[test]
+fn test_playlist_summary_record_failure() {
+    let mut summary = PlaylistSummary::default();
+
+    assert!(matches!(
+        summary.record_failure(ParrotError::DuplicateTrack),
+        Ok(true)
+    ));
+    assert_eq!(summary.duplicates, 1);
+
+    let limit = QueueLimit::TracksPerUser(3);
+    assert!(matches!(
+        summary.record_failure(ParrotError::LimitReached(limit)),
+        Ok(false)
+    ));
+    assert_eq!(summary.limit_hit, Some(limit));
+
+    assert!(summary
+        .record_failure(ParrotError::Other("unavailable"))
+        .is_err());
+}
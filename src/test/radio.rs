@@ -0,0 +1,85 @@
+use crate::sources::radio::{
+    parse_playlist, parse_stream_title, IcyDemuxer, PlaylistEntry, RadioPlaylist, RadioSource,
+};
+
+#[test]
+fn test_parse_radio_playlists() {
+    let pls = "[playlist]\nNumberOfEntries=2\nFile1=http://radio.example/stream\nTitle1=Example FM\nFile2=http://backup.example/stream\nLength2=-1\nVersion=2\n";
+    assert_eq!(
+        parse_playlist(pls),
+        RadioPlaylist::Streams(vec![
+            PlaylistEntry {
+                url: String::from("http://radio.example/stream"),
+                title: Some(String::from("Example FM")),
+            },
+            PlaylistEntry {
+                url: String::from("http://backup.example/stream"),
+                title: None,
+            },
+        ])
+    );
+
+    let m3u = "#EXTM3U\n#EXTINF:-1,Example FM\nhttp://radio.example/stream\n\nstream.aac\n";
+    assert_eq!(
+        parse_playlist(m3u),
+        RadioPlaylist::Streams(vec![
+            PlaylistEntry {
+                url: String::from("http://radio.example/stream"),
+                title: Some(String::from("Example FM")),
+            },
+            PlaylistEntry {
+                url: String::from("stream.aac"),
+                title: None,
+            },
+        ])
+    );
+
+    let hls = "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:10,\nsegment0.ts\n";
+    assert_eq!(parse_playlist(hls), RadioPlaylist::Hls);
+
+    assert!(RadioSource::is_playlist_url(
+        "http://radio.example/listen.pls"
+    ));
+    assert!(RadioSource::is_playlist_url(
+        "https://radio.example/live.M3U8?token=1"
+    ));
+    assert!(!RadioSource::is_playlist_url(
+        "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+    ));
+    assert!(!RadioSource::is_playlist_url("library:Album/list.m3u"));
+}
+
+#[test]
+fn test_icy_demuxer() {
+    let metadata = "StreamTitle='Artist - It's a Song';StreamUrl='';";
+    let mut block = metadata.as_bytes().to_vec();
+    block.resize(64, 0);
+
+    // 4 bytes of audio, a metadata block, 4 more bytes of audio and an empty block
+    let mut stream = b"abcd".to_vec();
+    stream.push(4);
+    stream.extend_from_slice(&block);
+    stream.extend_from_slice(b"efgh");
+    stream.push(0);
+    stream.extend_from_slice(b"ij");
+
+    // the blocks have to be found however the stream happens to be split up
+    for chunk_size in [1, 3, 7, stream.len()] {
+        let mut demuxer = IcyDemuxer::new(4);
+        let mut audio = Vec::new();
+        let mut titles = Vec::new();
+
+        for chunk in stream.chunks(chunk_size) {
+            titles.extend(demuxer.demux(chunk, &mut audio));
+        }
+
+        assert_eq!(audio, b"abcdefghij");
+        assert_eq!(titles, vec![String::from("Artist - It's a Song")]);
+    }
+
+    assert_eq!(parse_stream_title("StreamTitle='';"), None);
+    assert_eq!(
+        parse_stream_title("StreamTitle='Unterminated\0\0\0"),
+        Some(String::from("Unterminated"))
+    );
+}
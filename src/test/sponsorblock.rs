@@ -0,0 +1,68 @@
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::sources::sponsorblock::{SkipSegment, SponsorBlock};
+
+/// Serves a single canned response, handing back the request line it received.
+fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0; 4096];
+        let len = stream.read(&mut request).unwrap();
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+
+        let request = String::from_utf8_lossy(&request[..len]).to_string();
+        request.lines().next().unwrap_or_default().to_string()
+    });
+
+    (base_url, handle)
+}
+
+#[tokio::test]
+async fn test_fetch_segments() {
+    let (base_url, handle) = serve_once(
+        "200 OK",
+        r#"[{"segment":[0,12.5],"category":"intro"},{"segment":[200,190],"category":"outro"}]"#,
+    );
+
+    let segments = SponsorBlock::fetch_segments(&base_url, "dQw4w9WgXcQ")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        segments,
+        vec![SkipSegment {
+            start: Duration::ZERO,
+            end: Duration::from_millis(12500),
+        }]
+    );
+
+    let request_line = handle.join().unwrap();
+    assert!(request_line.starts_with("GET /api/skipSegments?videoID=dQw4w9WgXcQ&"));
+}
+
+#[tokio::test]
+async fn test_fetch_segments_not_found() {
+    let (base_url, handle) = serve_once("404 Not Found", "Not Found");
+
+    let segments = SponsorBlock::fetch_segments(&base_url, "dQw4w9WgXcQ")
+        .await
+        .unwrap();
+    assert!(segments.is_empty());
+
+    handle.join().unwrap();
+}
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use crate::utils::get_human_readable_timestamp;
+use crate::utils::{
+    get_human_readable_timestamp, get_playback_range, get_youtube_video_id, parse_seek_target,
+    parse_timestamp, parse_url_timestamp, PlaybackRange, SeekTarget,
+};
 
 #[test]
 fn test_get_human_readable_timestamp() {
@@ -22,3 +25,84 @@ fn test_get_human_readable_timestamp() {
     let result = get_human_readable_timestamp(None);
     assert_eq!(result, "∞");
 }
+
+#[test]
+fn test_get_youtube_video_id() {
+    let result = get_youtube_video_id("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42");
+    assert_eq!(result, Some("dQw4w9WgXcQ".to_string()));
+
+    let result = get_youtube_video_id("https://youtu.be/dQw4w9WgXcQ");
+    assert_eq!(result, Some("dQw4w9WgXcQ".to_string()));
+
+    let result = get_youtube_video_id("https://www.youtube.com/playlist?list=PL123");
+    assert_eq!(result, None);
+
+    let result = get_youtube_video_id("https://soundcloud.com/artist/track");
+    assert_eq!(result, None);
+}
+
+#[test]
+fn test_parse_url_timestamp() {
+    assert_eq!(parse_url_timestamp("95"), Some(Duration::from_secs(95)));
+    assert_eq!(parse_url_timestamp("1m35s"), Some(Duration::from_secs(95)));
+    assert_eq!(parse_url_timestamp("1h2s"), Some(Duration::from_secs(3602)));
+    assert_eq!(parse_url_timestamp("1m35"), None);
+    assert_eq!(parse_url_timestamp("-5"), None);
+    assert_eq!(parse_url_timestamp("abc"), None);
+    assert_eq!(parse_url_timestamp("1e20"), None);
+    assert_eq!(parse_url_timestamp("inf"), None);
+    assert_eq!(parse_url_timestamp("18446744073709551615h"), None);
+    assert_eq!(parse_url_timestamp("99999999999999999999s"), None);
+}
+
+#[test]
+fn test_get_playback_range() {
+    let result = get_playback_range("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=1m35s");
+    assert_eq!(result.start, Some(Duration::from_secs(95)));
+    assert_eq!(result.end, None);
+
+    let result = get_playback_range("https://www.youtube.com/embed/dQw4w9WgXcQ?start=30&end=90");
+    assert_eq!(result.start, Some(Duration::from_secs(30)));
+    assert_eq!(result.end, Some(Duration::from_secs(90)));
+
+    let result = get_playback_range("https://example.com/track.mp3#t=30,90");
+    assert_eq!(result.start, Some(Duration::from_secs(30)));
+    assert_eq!(result.end, Some(Duration::from_secs(90)));
+
+    let result = get_playback_range("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=0&end=0");
+    assert_eq!(result, PlaybackRange::default());
+
+    let result = get_playback_range("https://www.youtube.com/watch?v=dQw4w9WgXcQ&start=90&end=30");
+    assert_eq!(result.start, Some(Duration::from_secs(90)));
+    assert_eq!(result.end, None);
+}
+
+#[test]
+fn test_parse_timestamp() {
+    assert_eq!(parse_timestamp("1:23"), Some(Duration::from_secs(83)));
+    assert_eq!(parse_timestamp("1:02:03"), Some(Duration::from_secs(3723)));
+    assert_eq!(parse_timestamp("90:00"), Some(Duration::from_secs(5400)));
+    assert_eq!(parse_timestamp("45"), Some(Duration::from_secs(45)));
+    assert_eq!(parse_timestamp("1h2m"), Some(Duration::from_secs(3720)));
+    assert_eq!(parse_timestamp("1:60"), None);
+    assert_eq!(parse_timestamp("1:2:3:4"), None);
+    assert_eq!(parse_timestamp("1:"), None);
+    assert_eq!(parse_timestamp(""), None);
+    assert_eq!(parse_timestamp("1e20"), None);
+    assert_eq!(parse_timestamp("99999999999999999999m"), None);
+    assert_eq!(parse_timestamp("999999999999999999:00:00"), None);
+}
+
+#[test]
+fn test_parse_seek_target() {
+    let result = parse_seek_target("1:23");
+    assert_eq!(result, Some(SeekTarget::Absolute(Duration::from_secs(83))));
+
+    let result = parse_seek_target("+30s");
+    assert_eq!(result, Some(SeekTarget::Forward(Duration::from_secs(30))));
+
+    let result = parse_seek_target("-1:00");
+    assert_eq!(result, Some(SeekTarget::Backward(Duration::from_secs(60))));
+
+    assert_eq!(parse_seek_target("+"), None);
+}
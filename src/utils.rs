@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use serenity::{
     all::{
         CommandInteraction, CreateEmbedAuthor, CreateEmbedFooter, CreateInteractionResponse,
@@ -6,14 +7,20 @@ use serenity::{
     builder::CreateEmbed,
     http::{Http, HttpError},
     model::channel::Message,
-    prelude::TypeMapKey,
+    model::id::UserId,
+    prelude::{Mentionable, TypeMap, TypeMapKey},
     Error,
 };
 use songbird::{input::AuxMetadata, tracks::TrackHandle};
 use std::{sync::Arc, time::Duration};
 use url::Url;
 
-use crate::{errors::ParrotError, messaging::message::ParrotMessage};
+use crate::{
+    errors::ParrotError,
+    filters::{calculate_tempo, format_filters, format_playback_rate, AudioFilters, PlaybackRate},
+    messaging::{message::ParrotMessage, messages::TRACK_REQUESTED_BY},
+    sources::chapters::{find_chapter, Chapter},
+};
 
 pub struct AuxMetadataTypeMapKey;
 
@@ -21,6 +28,56 @@ impl TypeMapKey for AuxMetadataTypeMapKey {
     type Value = AuxMetadata;
 }
 
+pub struct RequesterTypeMapKey;
+
+impl TypeMapKey for RequesterTypeMapKey {
+    type Value = UserId;
+}
+
+pub struct AudioFiltersTypeMapKey;
+
+impl TypeMapKey for AudioFiltersTypeMapKey {
+    type Value = AudioFilters;
+}
+
+pub struct PlaybackRateTypeMapKey;
+
+impl TypeMapKey for PlaybackRateTypeMapKey {
+    type Value = PlaybackRate;
+}
+
+pub struct PlaybackRangeTypeMapKey;
+
+impl TypeMapKey for PlaybackRangeTypeMapKey {
+    type Value = PlaybackRange;
+}
+
+pub struct LoopSectionTypeMapKey;
+
+impl TypeMapKey for LoopSectionTypeMapKey {
+    type Value = LoopSection;
+}
+
+/// Marks a track that already has a handler looping its section.
+pub struct LoopSectionHandlerTypeMapKey;
+
+impl TypeMapKey for LoopSectionHandlerTypeMapKey {
+    type Value = ();
+}
+
+pub struct ChaptersTypeMapKey;
+
+impl TypeMapKey for ChaptersTypeMapKey {
+    type Value = Vec<Chapter>;
+}
+
+/// Marks a track that was swapped out for a recreated copy of itself.
+pub struct ReplacedTypeMapKey;
+
+impl TypeMapKey for ReplacedTypeMapKey {
+    type Value = ();
+}
+
 pub async fn create_response(
     http: &Arc<Http>,
     interaction: &mut CommandInteraction,
@@ -111,11 +168,27 @@ pub async fn create_now_playing_embed(track: &TrackHandle) -> CreateEmbed {
             "{}",
             ParrotMessage::NowPlaying
         )))
-        .title(metadata.title.unwrap())
-        .url(metadata.source_url.clone().unwrap());
+        .title(metadata.title.unwrap());
+
+    // uploads and library tracks have no page to link to
+    let footer_info = get_footer_info(metadata.source_url.as_deref().unwrap());
+    if footer_info.is_some() {
+        embed = embed.url(metadata.source_url.clone().unwrap());
+    }
 
-    let position = get_human_readable_timestamp(Some(track.get_info().await.unwrap().position));
-    let duration = get_human_readable_timestamp(metadata.duration);
+    // the position advances at the playback rate, so the duration needs to match
+    let tempo = get_playback_tempo(&track_typemap_read_lock);
+    let position = track.get_info().await.unwrap().position;
+    let chapter = track_typemap_read_lock
+        .get::<ChaptersTypeMapKey>()
+        .and_then(|chapters| {
+            let index = find_chapter(chapters, position.mul_f64(tempo))?;
+            Some(chapters[index].title.clone())
+        });
+
+    let position = get_human_readable_timestamp(Some(position));
+    let duration =
+        get_human_readable_timestamp(metadata.duration.map(|duration| duration.div_f64(tempo)));
 
     embed = embed.field("Progress", format!(">>> {} / {}", position, duration), true);
 
@@ -124,26 +197,80 @@ pub async fn create_now_playing_embed(track: &TrackHandle) -> CreateEmbed {
         None => embed.field("Channel", ">>> N/A", true),
     };
 
-    embed = embed.thumbnail(metadata.thumbnail.unwrap());
+    if let Some(requester) = track_typemap_read_lock.get::<RequesterTypeMapKey>() {
+        embed = embed.field(
+            TRACK_REQUESTED_BY,
+            format!(">>> {}", requester.mention()),
+            true,
+        );
+    }
+
+    if let Some(chapter) = chapter {
+        embed = embed.field("Chapter", format!(">>> {}", chapter), false);
+    }
 
-    let source_url = metadata.source_url.unwrap();
+    if let Some(filters) = track_typemap_read_lock.get::<AudioFiltersTypeMapKey>() {
+        if !filters.is_empty() {
+            embed = embed.field("Filters", format!(">>> {}", format_filters(filters)), false);
+        }
+    }
 
-    let (footer_text, footer_icon_url) = get_footer_info(&source_url);
+    if let Some(rate) = track_typemap_read_lock.get::<PlaybackRateTypeMapKey>() {
+        if rate.speed != 1.0 {
+            embed = embed.field(
+                "Speed",
+                format!(">>> {}", format_playback_rate(rate.speed)),
+                true,
+            );
+        }
 
-    embed.footer(CreateEmbedFooter::new(footer_text).icon_url(footer_icon_url))
+        if rate.pitch != 1.0 {
+            embed = embed.field(
+                "Pitch",
+                format!(">>> {}", format_playback_rate(rate.pitch)),
+                true,
+            );
+        }
+    }
+
+    if let Some(thumbnail) = metadata.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+
+    match footer_info {
+        Some((footer_text, footer_icon_url)) => {
+            embed.footer(CreateEmbedFooter::new(footer_text).icon_url(footer_icon_url))
+        }
+        None => embed,
+    }
 }
 
-pub fn get_footer_info(url: &str) -> (String, String) {
-    let url_data = Url::parse(url).unwrap();
-    let domain = url_data.host_str().unwrap();
+/// How much faster than its source a track plays back, given its typemap.
+pub fn get_playback_tempo(typemap: &TypeMap) -> f64 {
+    let filters = typemap
+        .get::<AudioFiltersTypeMapKey>()
+        .cloned()
+        .unwrap_or_default();
+    let rate = typemap
+        .get::<PlaybackRateTypeMapKey>()
+        .copied()
+        .unwrap_or_default();
+
+    calculate_tempo(&filters, &rate)
+}
+
+/// The footer naming the site a track streams from, or `None` if it isn't from a website.
+pub fn get_footer_info(url: &str) -> Option<(String, String)> {
+    let url_data = Url::parse(url).ok()?;
+    let domain = url_data.host_str()?;
 
     // remove www prefix because it looks ugly
     let domain = domain.replace("www.", "");
 
-    (
+    Some((
         format!("Streaming via {}", domain),
         format!("https://www.google.com/s2/favicons?domain={}", domain),
-    )
+    ))
 }
 
 pub fn get_human_readable_timestamp(duration: Option<Duration>) -> String {
@@ -164,6 +291,157 @@ pub fn get_human_readable_timestamp(duration: Option<Duration>) -> String {
     }
 }
 
+/// The part of a track a link asks to be played, e.g. `?t=95` or `?start=30&end=90`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PlaybackRange {
+    pub start: Option<Duration>,
+    pub end: Option<Duration>,
+}
+
+/// A part of a track that's played over and over, timed against the source.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoopSection {
+    pub start: Duration,
+    pub end: Duration,
+}
+
+/// Reads the start and end offsets from a link's query string or `#t=` fragment.
+pub fn get_playback_range(url: &str) -> PlaybackRange {
+    let Ok(url_data) = Url::parse(url) else {
+        return PlaybackRange::default();
+    };
+
+    let mut range = PlaybackRange::default();
+
+    for (key, value) in url_data.query_pairs() {
+        match key.as_ref() {
+            "t" | "start" | "time_continue" => range.start = parse_url_timestamp(&value),
+            "end" => range.end = parse_url_timestamp(&value),
+            _ => {}
+        }
+    }
+
+    // media fragments may carry both ends, as in `#t=30,90`
+    if let Some(fragment) = url_data.fragment().and_then(|f| f.strip_prefix("t=")) {
+        let mut offsets = fragment.splitn(2, ',');
+        range.start = offsets.next().and_then(parse_url_timestamp).or(range.start);
+        range.end = offsets.next().and_then(parse_url_timestamp).or(range.end);
+    }
+
+    range.start = range.start.filter(|start| !start.is_zero());
+    range.end = range
+        .end
+        .filter(|end| *end > range.start.unwrap_or_default());
+    range
+}
+
+/// Where a seek should land, either a point in the track or an offset from the current one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SeekTarget {
+    Absolute(Duration),
+    Forward(Duration),
+    Backward(Duration),
+}
+
+/// Parses a seek as users type it, e.g. `1:23`, `+30s` or `-1:00`.
+pub fn parse_seek_target(value: &str) -> Option<SeekTarget> {
+    let value = value.trim();
+
+    if let Some(offset) = value.strip_prefix('+') {
+        parse_timestamp(offset).map(SeekTarget::Forward)
+    } else if let Some(offset) = value.strip_prefix('-') {
+        parse_timestamp(offset).map(SeekTarget::Backward)
+    } else {
+        parse_timestamp(value).map(SeekTarget::Absolute)
+    }
+}
+
+/// Parses a timestamp written as `hh:mm:ss`, `mm:ss`, plain seconds or with units like `1h2m`.
+pub fn parse_timestamp(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if !value.contains(':') {
+        return parse_url_timestamp(value).filter(|_| !value.is_empty());
+    }
+
+    let units = value
+        .split(':')
+        .map(|unit| unit.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    // only the leading unit may overflow into the next one, as in `90:00`
+    if units.len() > 3 || units.iter().skip(1).any(|unit| *unit >= 60) {
+        return None;
+    }
+
+    let seconds = units.iter().try_fold(0u64, |total, unit| {
+        total.checked_mul(60)?.checked_add(*unit)
+    })?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// Parses offsets as links write them, either plain seconds (`95`) or with units (`1m35s`).
+pub fn parse_url_timestamp(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(seconds).ok();
+    }
+
+    let mut seconds: u64 = 0;
+    let mut digits = String::new();
+
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+
+        let amount = digits.parse::<u64>().ok()?.checked_mul(unit)?;
+        seconds = seconds.checked_add(amount)?;
+        digits.clear();
+    }
+
+    if !digits.is_empty() {
+        return None;
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// Converts a volume percentage into the gain multiplier songbird expects.
+pub fn volume_to_gain(volume: u8) -> f32 {
+    f32::from(volume) / 100.0
+}
+
 pub fn compare_domains(domain: &str, subdomain: &str) -> bool {
     subdomain == domain || subdomain.ends_with(domain)
 }
+
+/// Extracts the video ID from a YouTube (or youtu.be) link.
+pub fn get_youtube_video_id(url: &str) -> Option<String> {
+    let url_data = Url::parse(url).ok()?;
+    let domain = url_data.host_str()?;
+
+    if compare_domains("youtu.be", domain) {
+        return url_data
+            .path_segments()?
+            .next()
+            .filter(|id| !id.is_empty())
+            .map(String::from);
+    }
+
+    if !compare_domains("youtube.com", domain) {
+        return None;
+    }
+
+    url_data
+        .query_pairs()
+        .find(|(key, _)| key == "v")
+        .map(|(_, id)| id.into_owned())
+}